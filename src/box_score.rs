@@ -0,0 +1,732 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::earned_runs::compute_earned_runs;
+use crate::parser::game::{
+    AttendanceInfo, Base, EventModifier, EventType, FieldingCreditType, Game, LineScore, Play, Position, Team,
+    TimeOfGameInfo,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BattingLine {
+    pub player_id: String,
+    pub name: String,
+    /// Every position the player appeared at, in order.
+    pub positions: Vec<Position>,
+    pub at_bats: u8,
+    pub runs: u8,
+    pub hits: u8,
+    pub rbi: u8,
+    pub walks: u8,
+    pub strikeouts: u8,
+    pub doubles: u8,
+    pub triples: u8,
+    pub home_runs: u8,
+    pub stolen_bases: u8,
+    pub caught_stealing: u8,
+    /// Runners left on base when the player made an out.
+    pub left_on_base: u8,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PitchingLine {
+    pub player_id: String,
+    pub name: String,
+    pub outs: u8,
+    pub hits: u8,
+    pub runs: u8,
+    pub earned_runs: u8,
+    pub walks: u8,
+    pub strikeouts: u8,
+    pub home_runs: u8,
+    pub batters_faced: u8,
+    /// Only known if the game records pitches.
+    pub pitches: Option<u16>,
+}
+
+impl PitchingLine {
+    /// Innings pitched as written in a box score, where `6.1` is six and a third.
+    pub fn get_innings_pitched(&self) -> String {
+        format!("{}.{}", self.outs / 3, self.outs % 3)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldingLine {
+    pub player_id: String,
+    pub name: String,
+    pub putouts: u8,
+    pub assists: u8,
+    pub errors: u8,
+}
+
+/// One team's side of the box score. The footnote lists hold player ids with their totals,
+/// in the order the players first did it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TeamBox {
+    pub name: String,
+    pub batting: Vec<BattingLine>,
+    pub pitching: Vec<PitchingLine>,
+    pub fielding: Vec<FieldingLine>,
+    pub runs: u8,
+    pub hits: u8,
+    pub errors: u8,
+    pub left_on_base: u8,
+    pub double_plays: u8,
+    pub triple_plays: u8,
+    pub doubles: Vec<(String, u8)>,
+    pub triples: Vec<(String, u8)>,
+    pub home_runs: Vec<(String, u8)>,
+    pub stolen_bases: Vec<(String, u8)>,
+    pub caught_stealing: Vec<(String, u8)>,
+    pub sacrifice_hits: Vec<(String, u8)>,
+    pub sacrifice_flies: Vec<(String, u8)>,
+    /// Batters hit by the team's pitchers, with the pitcher who hit them.
+    pub hit_by_pitch: Vec<(String, String)>,
+    pub wild_pitches: Vec<(String, u8)>,
+    pub passed_balls: Vec<(String, u8)>,
+    pub fielding_errors: Vec<(String, u8)>,
+}
+
+impl TeamBox {
+    fn batter(&mut self, player_id: &str) -> Option<&mut BattingLine> {
+        self.batting.iter_mut().find(|line| line.player_id == player_id)
+    }
+
+    fn pitcher(&mut self, player_id: &str) -> &mut PitchingLine {
+        let index = match self.pitching.iter().position(|line| line.player_id == player_id) {
+            Some(index) => index,
+            None => {
+                self.pitching.push(PitchingLine {
+                    player_id: player_id.to_string(),
+                    ..Default::default()
+                });
+                self.pitching.len() - 1
+            },
+        };
+
+        &mut self.pitching[index]
+    }
+
+    fn fielder(&mut self, player_id: &str) -> &mut FieldingLine {
+        let index = match self.fielding.iter().position(|line| line.player_id == player_id) {
+            Some(index) => index,
+            None => {
+                self.fielding.push(FieldingLine {
+                    player_id: player_id.to_string(),
+                    ..Default::default()
+                });
+                self.fielding.len() - 1
+            },
+        };
+
+        &mut self.fielding[index]
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoxScore {
+    pub game_id: String,
+    pub date: Option<String>,
+    pub line_score: LineScore,
+    pub visiting: TeamBox,
+    pub home: TeamBox,
+    /// Umpire ids by where they stood, such as `HP` or `1B`.
+    pub umpires: Vec<(String, String)>,
+    /// In minutes.
+    pub time_of_game: Option<u16>,
+    pub attendance: Option<u32>,
+    names: HashMap<String, String>,
+}
+
+/// Adds one to the player's entry in a footnote list.
+fn tally(list: &mut Vec<(String, u8)>, player_id: &str) {
+    match list.iter_mut().find(|(id, _)| id == player_id) {
+        Some((_, count)) => *count += 1,
+        None => list.push((player_id.to_string(), 1)),
+    }
+}
+
+impl BoxScore {
+    pub fn from_game(game: &Game) -> Self {
+        let info = game.get_info_state();
+
+        let mut box_score = BoxScore {
+            game_id: game.get_id().to_string(),
+            date: info.date.clone(),
+            line_score: game.get_line_score(),
+            visiting: TeamBox {
+                name: info.visiting_team.clone().unwrap_or_default(),
+                ..Default::default()
+            },
+            home: TeamBox {
+                name: info.home_team.clone().unwrap_or_default(),
+                ..Default::default()
+            },
+            umpires: [
+                ("HP", &info.umpire_home),
+                ("1B", &info.umpire_1b),
+                ("2B", &info.umpire_2b),
+                ("3B", &info.umpire_3b),
+                ("LF", &info.umpire_left_field),
+                ("RF", &info.umpire_right_field),
+            ]
+            .into_iter()
+            .filter_map(|(label, umpire)| umpire.as_ref().map(|umpire| (label.to_string(), umpire.to_string())))
+            .collect(),
+            time_of_game: match info.time_of_game {
+                Some(TimeOfGameInfo::Known(minutes)) => Some(minutes),
+                _ => None,
+            },
+            attendance: match info.attendance {
+                Some(AttendanceInfo::Known(attendance)) => Some(attendance),
+                _ => None,
+            },
+            names: HashMap::new(),
+        };
+
+        box_score.add_lineups(game);
+
+        for (index, play) in game.get_plays().iter().enumerate() {
+            box_score.add_batting(play);
+            box_score.add_pitching(play);
+            box_score.add_fielding(play);
+
+            // runners still on base at the end of a half inning were left there
+            let next = game.get_plays().get(index + 1);
+            if next.is_none_or(|next| next.get_inning() != play.get_inning() || next.get_team() != play.get_team()) {
+                box_score.get_team_mut(&play.get_team()).left_on_base += play.get_bases_after().iter().flatten().count() as u8;
+            }
+        }
+
+        let earned_runs = compute_earned_runs(game);
+        for team_box in [&mut box_score.visiting, &mut box_score.home] {
+            for line in &mut team_box.pitching {
+                line.name = box_score.names.get(&line.player_id).cloned().unwrap_or_default();
+                line.earned_runs = earned_runs.get_pitcher(&line.player_id).map_or(0, |p| p.earned_runs);
+            }
+
+            for line in &mut team_box.fielding {
+                line.name = box_score.names.get(&line.player_id).cloned().unwrap_or_default();
+            }
+
+            team_box.runs = team_box.batting.iter().map(|line| line.runs).sum();
+            team_box.hits = team_box.batting.iter().map(|line| line.hits).sum();
+            team_box.errors = team_box.fielding.iter().map(|line| line.errors).sum();
+        }
+
+        box_score
+    }
+
+    pub fn get_team(&self, team: &Team) -> &TeamBox {
+        match team {
+            Team::Visiting => &self.visiting,
+            Team::Home => &self.home,
+        }
+    }
+
+    fn get_team_mut(&mut self, team: &Team) -> &mut TeamBox {
+        match team {
+            Team::Visiting => &mut self.visiting,
+            Team::Home => &mut self.home,
+        }
+    }
+
+    /// Sets up a batting line for everyone who took a place in the batting order, grouped by
+    /// lineup slot, and a fielding line for everyone who played in the field.
+    fn add_lineups(&mut self, game: &Game) {
+        for team in [Team::Visiting, Team::Home] {
+            let mut entries = game.get_lineup_entries()
+                .iter()
+                .filter(|entry| entry.team == team)
+                .collect::<Vec<_>>();
+            entries.sort_by_key(|entry| entry.batting_order);
+
+            for entry in entries {
+                self.names.insert(entry.player_id.to_string(), entry.player_name.to_string());
+                let team_box = self.get_team_mut(&team);
+
+                if entry.position.is_fielding() && entry.position != Position::DesignatedHitter {
+                    team_box.fielder(&entry.player_id);
+                }
+
+                if entry.batting_order == 0 {
+                    continue;
+                }
+
+                match team_box.batter(&entry.player_id) {
+                    Some(line) => {
+                        if !line.positions.contains(&entry.position) {
+                            line.positions.push(entry.position);
+                        }
+                    },
+                    None => team_box.batting.push(BattingLine {
+                        player_id: entry.player_id.to_string(),
+                        name: entry.player_name.to_string(),
+                        positions: vec![entry.position],
+                        ..Default::default()
+                    }),
+                }
+            }
+        }
+    }
+
+    fn add_batting(&mut self, play: &Play) {
+        let Some(event) = play.get_event() else {
+            return;
+        };
+
+        let team_box = self.get_team_mut(&play.get_team());
+        let batter_id = play.get_batter_id();
+
+        for runner_advance in play.get_runner_advances().iter().filter(|ra| ra.advance.is_run()) {
+            if let Some(line) = team_box.batter(&runner_advance.runner.id) {
+                line.runs += 1;
+            }
+        }
+
+        for event_type in event.event_type.with_base_running_event() {
+            let stolen_bases = match event_type {
+                EventType::StolenBase { bases } => bases.clone(),
+                _ => Vec::new(),
+            };
+
+            for base in stolen_bases {
+                if let Some(runner) = play.get_runner(&base.previous()) {
+                    tally(&mut team_box.stolen_bases, &runner.id);
+                    if let Some(line) = team_box.batter(&runner.id) {
+                        line.stolen_bases += 1;
+                    }
+                }
+            }
+
+            let caught_stealing = match event_type {
+                EventType::CaughtStealing { base, .. } | EventType::Pickoff { caught_stealing: true, base, .. } => Some(base.previous()),
+                _ => None,
+            };
+
+            if let Some(runner) = caught_stealing.and_then(|base| play.get_runner(&base)) {
+                tally(&mut team_box.caught_stealing, &runner.id);
+                if let Some(line) = team_box.batter(&runner.id) {
+                    line.caught_stealing += 1;
+                }
+            }
+        }
+
+        if !event.event_type.ends_plate_appearance() {
+            return;
+        }
+
+        let batter_out = play.get_runner_advances()
+            .iter()
+            .any(|ra| ra.advance.starting_base == Base::Home && ra.advance.out);
+        let rbi = play.get_rbi();
+        let left_on_base = play.get_bases_after().iter().flatten().count() as u8;
+
+        match event.event_type.total_bases() {
+            2 => tally(&mut team_box.doubles, batter_id),
+            3 => tally(&mut team_box.triples, batter_id),
+            4 => tally(&mut team_box.home_runs, batter_id),
+            _ => {},
+        }
+
+        if event.modifiers.contains(&EventModifier::SacrificeHitOrBunt) {
+            tally(&mut team_box.sacrifice_hits, batter_id);
+        }
+
+        if event.modifiers.contains(&EventModifier::SacrificeFly) {
+            tally(&mut team_box.sacrifice_flies, batter_id);
+        }
+
+        let Some(line) = team_box.batter(batter_id) else {
+            return;
+        };
+
+        line.at_bats += event.is_at_bat() as u8;
+        line.hits += event.event_type.is_hit() as u8;
+        line.rbi += rbi;
+        line.walks += matches!(event.event_type, EventType::Walk { .. }) as u8;
+        line.strikeouts += matches!(event.event_type, EventType::Strikeout { .. }) as u8;
+        match event.event_type.total_bases() {
+            2 => line.doubles += 1,
+            3 => line.triples += 1,
+            4 => line.home_runs += 1,
+            _ => {},
+        }
+
+        if batter_out {
+            line.left_on_base += left_on_base;
+        }
+    }
+
+    fn add_pitching(&mut self, play: &Play) {
+        let Some(event) = play.get_event() else {
+            return;
+        };
+
+        let fielding_team = play.get_team().opponent();
+
+        // runs go to whoever put the runner on base, who may have left the game already
+        for runner_advance in play.get_runner_advances().iter().filter(|ra| ra.advance.is_run()) {
+            if let Some(pitcher_id) = &runner_advance.runner.pitcher_id {
+                self.get_team_mut(&fielding_team).pitcher(pitcher_id).runs += 1;
+            }
+        }
+
+        let Some(pitcher_id) = play.get_pitcher_id() else {
+            return;
+        };
+
+        let catcher_id = play.get_defense().get(&Position::Catcher).cloned();
+        let batter_id = play.get_batter_id().to_string();
+        let team_box = self.get_team_mut(&fielding_team);
+
+        for event_type in event.event_type.with_base_running_event() {
+            match event_type {
+                EventType::WildPitch => tally(&mut team_box.wild_pitches, pitcher_id),
+                EventType::PassedBall => {
+                    if let Some(catcher_id) = &catcher_id {
+                        tally(&mut team_box.passed_balls, catcher_id);
+                    }
+                },
+                EventType::HitByPitch => team_box.hit_by_pitch.push((batter_id.to_string(), pitcher_id.to_string())),
+                _ => {},
+            }
+        }
+
+        let line = team_box.pitcher(pitcher_id);
+        line.outs += play.get_runner_advances().iter().filter(|ra| ra.advance.out).count() as u8;
+
+        if play.get_pitches().is_some() {
+            *line.pitches.get_or_insert(0) += play.get_pitches_thrown().len() as u16;
+        }
+
+        if event.event_type.ends_plate_appearance() {
+            line.batters_faced += 1;
+            line.hits += event.event_type.is_hit() as u8;
+            line.walks += matches!(event.event_type, EventType::Walk { .. }) as u8;
+            line.strikeouts += matches!(event.event_type, EventType::Strikeout { .. }) as u8;
+            line.home_runs += (event.event_type.total_bases() == 4) as u8;
+        }
+    }
+
+    fn add_fielding(&mut self, play: &Play) {
+        let fielding_team = play.get_team().opponent();
+        let team_box = self.get_team_mut(&fielding_team);
+
+        if let Some(event) = play.get_event() {
+            team_box.double_plays += event.is_double_play() as u8;
+            team_box.triple_plays += event.is_triple_play() as u8;
+        }
+
+        for credit in play.get_fielding_credits() {
+            let Some(player_id) = &credit.player_id else {
+                continue;
+            };
+
+            let line = team_box.fielder(player_id);
+            match credit.credit_type {
+                FieldingCreditType::Putout => line.putouts += 1,
+                FieldingCreditType::Assist => line.assists += 1,
+                FieldingCreditType::Error => {
+                    line.errors += 1;
+                    tally(&mut team_box.fielding_errors, player_id);
+                },
+            }
+        }
+    }
+
+    fn name(&self, player_id: &str) -> String {
+        self.names.get(player_id).cloned().unwrap_or(player_id.to_string())
+    }
+
+    /// A footnote such as `2B -- Span 2, Hudson`, or nothing if no one qualified.
+    fn footnote(&self, label: &str, lists: [&Vec<(String, u8)>; 2]) -> Option<String> {
+        let players = lists
+            .into_iter()
+            .flatten()
+            .map(|(player_id, count)| {
+                if *count > 1 { format!("{} {count}", self.name(player_id)) } else { self.name(player_id) }
+            })
+            .collect::<Vec<String>>();
+
+        if players.is_empty() {
+            None
+        } else {
+            Some(format!("{label} -- {}", players.join(", ")))
+        }
+    }
+
+    fn team_footnote(&self, label: &str, counts: [u8; 2]) -> Option<String> {
+        let teams = [&self.visiting, &self.home]
+            .into_iter()
+            .zip(counts)
+            .filter(|(_, count)| *count > 0)
+            .map(|(team_box, count)| format!("{} {count}", team_box.name))
+            .collect::<Vec<String>>();
+
+        if teams.is_empty() {
+            None
+        } else {
+            Some(format!("{label} -- {}", teams.join(", ")))
+        }
+    }
+
+    /// Renders the box score as plain text in the layout of `cwbox`.
+    pub fn render(&self) -> String {
+        let mut lines = Vec::new();
+
+        lines.push(format!(
+            "{} {} @ {} {}{}",
+            self.visiting.name,
+            self.visiting.runs,
+            self.home.name,
+            self.home.runs,
+            self.date.as_ref().map(|date| format!(" -- {date}")).unwrap_or_default(),
+        ));
+
+        for team_box in [&self.visiting, &self.home] {
+            lines.push(String::new());
+            lines.push(format!("{:<24}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}", team_box.name, "AB", "R", "H", "RBI", "BB", "SO", "LOB"));
+
+            for line in &team_box.batting {
+                let positions = line.positions.iter().map(position_abbreviation).collect::<Vec<&str>>().join("-");
+                lines.push(format!(
+                    "{:<24}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}",
+                    format!("{}, {positions}", line.name),
+                    line.at_bats,
+                    line.runs,
+                    line.hits,
+                    line.rbi,
+                    line.walks,
+                    line.strikeouts,
+                    line.left_on_base,
+                ));
+            }
+
+            let total = |f: fn(&BattingLine) -> u8| team_box.batting.iter().map(f).sum::<u8>();
+            lines.push(format!(
+                "{:<24}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}",
+                "TOTALS",
+                total(|line| line.at_bats),
+                total(|line| line.runs),
+                total(|line| line.hits),
+                total(|line| line.rbi),
+                total(|line| line.walks),
+                total(|line| line.strikeouts),
+                total(|line| line.left_on_base),
+            ));
+        }
+
+        lines.push(String::new());
+        let innings = self.line_score.visiting.len().max(self.line_score.home.len());
+        for (team_box, runs) in [(&self.visiting, &self.line_score.visiting), (&self.home, &self.line_score.home)] {
+            let mut innings_text = (0..innings)
+                .map(|inning| match runs.get(inning) {
+                    Some(runs) if *runs < 10 => runs.to_string(),
+                    Some(runs) => format!("({runs})"),
+                    None => "x".to_string(),
+                })
+                .collect::<Vec<String>>()
+                .chunks(3)
+                .map(|chunk| chunk.concat())
+                .collect::<Vec<String>>()
+                .join(" ");
+            innings_text = format!("{:<8}{innings_text}", team_box.name);
+
+            lines.push(format!("{innings_text} -- {:>2}", team_box.runs));
+        }
+
+        for team_box in [&self.visiting, &self.home] {
+            lines.push(String::new());
+            lines.push(format!(
+                "{:<24}{:>5}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>5}",
+                team_box.name, "IP", "H", "R", "ER", "BB", "SO", "HR", "BF", "PIT",
+            ));
+
+            for line in &team_box.pitching {
+                lines.push(format!(
+                    "{:<24}{:>5}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>4}{:>5}",
+                    line.name,
+                    line.get_innings_pitched(),
+                    line.hits,
+                    line.runs,
+                    line.earned_runs,
+                    line.walks,
+                    line.strikeouts,
+                    line.home_runs,
+                    line.batters_faced,
+                    line.pitches.map(|pitches| pitches.to_string()).unwrap_or_default(),
+                ));
+            }
+        }
+
+        let (visiting, home) = (&self.visiting, &self.home);
+        let hit_by_pitch = [visiting, home]
+            .into_iter()
+            .flat_map(|team_box| &team_box.hit_by_pitch)
+            .map(|(batter_id, pitcher_id)| format!("by {} ({})", self.name(pitcher_id), self.name(batter_id)))
+            .collect::<Vec<String>>();
+
+        let footnotes = [
+            self.footnote("E", [&visiting.fielding_errors, &home.fielding_errors]),
+            self.team_footnote("DP", [visiting.double_plays, home.double_plays]),
+            self.team_footnote("TP", [visiting.triple_plays, home.triple_plays]),
+            self.team_footnote("LOB", [visiting.left_on_base, home.left_on_base]),
+            self.footnote("2B", [&visiting.doubles, &home.doubles]),
+            self.footnote("3B", [&visiting.triples, &home.triples]),
+            self.footnote("HR", [&visiting.home_runs, &home.home_runs]),
+            self.footnote("SB", [&visiting.stolen_bases, &home.stolen_bases]),
+            self.footnote("CS", [&visiting.caught_stealing, &home.caught_stealing]),
+            self.footnote("SH", [&visiting.sacrifice_hits, &home.sacrifice_hits]),
+            self.footnote("SF", [&visiting.sacrifice_flies, &home.sacrifice_flies]),
+            (!hit_by_pitch.is_empty()).then(|| format!("HBP -- {}", hit_by_pitch.join(", "))),
+            self.footnote("WP", [&visiting.wild_pitches, &home.wild_pitches]),
+            self.footnote("PB", [&visiting.passed_balls, &home.passed_balls]),
+            (!self.umpires.is_empty()).then(|| {
+                let umpires = self.umpires.iter().map(|(label, id)| format!("{label} {id}")).collect::<Vec<String>>();
+                format!("U -- {}", umpires.join(", "))
+            }),
+            self.time_of_game.map(|minutes| format!("T -- {}:{:02}", minutes / 60, minutes % 60)),
+            self.attendance.map(|attendance| format!("A -- {attendance}")),
+        ];
+
+        lines.push(String::new());
+        lines.extend(footnotes.into_iter().flatten());
+
+        lines.join("\n") + "\n"
+    }
+}
+
+impl fmt::Display for BoxScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+fn position_abbreviation(position: &Position) -> &'static str {
+    match position {
+        Position::Pitcher => "p",
+        Position::Catcher => "c",
+        Position::FirstBase => "1b",
+        Position::SecondBase => "2b",
+        Position::ThirdBase => "3b",
+        Position::Shortstop => "ss",
+        Position::LeftField => "lf",
+        Position::CenterField => "cf",
+        Position::RightField => "rf",
+        Position::DesignatedHitter => "dh",
+        Position::PinchHitter => "ph",
+        Position::PinchRunner => "pr",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn box_score() -> BoxScore {
+        let games = fixtures::parse(fixtures::OPENING_INNING_FILE);
+        BoxScore::from_game(&games[0])
+    }
+
+    fn batting<'a>(team_box: &'a TeamBox, player_id: &str) -> &'a BattingLine {
+        team_box.batting.iter().find(|line| line.player_id == player_id).unwrap()
+    }
+
+    fn fielding<'a>(team_box: &'a TeamBox, player_id: &str) -> &'a FieldingLine {
+        team_box.fielding.iter().find(|line| line.player_id == player_id).unwrap()
+    }
+
+    #[test]
+    fn batting_lines() {
+        let box_score = box_score();
+
+        let order = box_score.visiting.batting.iter().map(|line| line.player_id.as_str()).collect::<Vec<&str>>();
+        assert_eq!(order, vec!["spand001", "hudso001", "mauej001", "cuddm001", "hardj001", "mornj001"]);
+
+        let hudson = batting(&box_score.visiting, "hudso001");
+        assert_eq!((hudson.at_bats, hudson.runs, hudson.hits, hudson.rbi), (1, 0, 1, 1));
+
+        let span = batting(&box_score.visiting, "spand001");
+        assert_eq!((span.at_bats, span.runs, span.hits, span.stolen_bases), (1, 1, 1, 1));
+
+        let matsui = batting(&box_score.home, "matsh001");
+        assert_eq!((matsui.at_bats, matsui.runs, matsui.hits, matsui.rbi, matsui.home_runs), (1, 1, 1, 2, 1));
+
+        let napoli = batting(&box_score.home, "napom001");
+        assert_eq!((napoli.at_bats, napoli.walks), (0, 1));
+
+        assert_eq!(batting(&box_score.home, "aybae001").strikeouts, 1);
+        assert_eq!(batting(&box_score.home, "morak001").left_on_base, 1);
+        assert_eq!(batting(&box_score.home, "abreb001").doubles, 1);
+
+        assert_eq!((box_score.visiting.runs, box_score.visiting.hits), (1, 2));
+        assert_eq!((box_score.home.runs, box_score.home.hits), (2, 2));
+    }
+
+    #[test]
+    fn pitching_lines() {
+        let box_score = box_score();
+
+        let baker = &box_score.visiting.pitching[0];
+        assert_eq!(baker.player_id, "bakes002");
+        assert_eq!(baker.get_innings_pitched(), "0.0");
+        assert_eq!((baker.hits, baker.runs, baker.earned_runs, baker.walks, baker.home_runs, baker.batters_faced), (2, 2, 2, 1, 1, 3));
+        assert_eq!(baker.pitches, Some(7));
+
+        let rauch = &box_score.visiting.pitching[1];
+        assert_eq!(rauch.get_innings_pitched(), "1.0");
+        assert_eq!((rauch.strikeouts, rauch.batters_faced, rauch.runs), (1, 3, 0));
+        assert_eq!(rauch.pitches, Some(6));
+
+        let weaver = &box_score.home.pitching[0];
+        assert_eq!((weaver.outs, weaver.hits, weaver.batters_faced), (3, 2, 4));
+        assert_eq!(weaver.pitches, Some(14));
+    }
+
+    #[test]
+    fn fielding_lines() {
+        let box_score = box_score();
+
+        let hardy = fielding(&box_score.visiting, "hardj001");
+        assert_eq!((hardy.putouts, hardy.assists, hardy.errors), (0, 1, 1));
+
+        let hudson = fielding(&box_score.visiting, "hudso001");
+        assert_eq!((hudson.putouts, hudson.assists, hudson.errors), (1, 1, 0));
+
+        assert_eq!(fielding(&box_score.visiting, "mauej001").putouts, 1);
+        assert_eq!(fielding(&box_score.home, "hunto001").putouts, 1);
+        assert_eq!(fielding(&box_score.home, "morak001").putouts, 1);
+
+        assert_eq!((box_score.visiting.errors, box_score.home.errors), (1, 0));
+        assert_eq!((box_score.visiting.double_plays, box_score.home.double_plays), (1, 1));
+        assert_eq!((box_score.visiting.left_on_base, box_score.home.left_on_base), (0, 1));
+    }
+
+    #[test]
+    fn render_footnotes() {
+        let rendered = box_score().render();
+
+        for line in [
+            "MIN 1 @ ANA 2 -- 2010/04/05",
+            "MIN     1 --  1",
+            "ANA     2 --  2",
+            "E -- J.J. Hardy",
+            "DP -- MIN 1, ANA 1",
+            "LOB -- ANA 1",
+            "2B -- Bobby Abreu",
+            "HR -- Hideki Matsui",
+            "SB -- Denard Span",
+            "U -- HP drakr901",
+            "T -- 2:44",
+            "A -- 43504",
+        ] {
+            assert!(rendered.lines().any(|l| l == line), "missing {line:?} in\n{rendered}");
+        }
+
+        assert!(rendered.contains("Orlando Hudson, 2b"));
+        assert!(rendered.contains("Jon Rauch"));
+    }
+}
@@ -0,0 +1,304 @@
+use strum_macros::Display;
+
+use crate::parser::game::{
+    BallPathNode, DayNightInfo, Event, EventType, FieldConditionInfo, GameTypeInfo, PitchesInfo, PrecipitationInfo, SkyInfo,
+    WindDirectionInfo,
+};
+
+/// The event type codes used by Chadwick's `cwevent` and the BEVENT tools. Displayed in
+/// snake case, as in `home_run`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
+#[strum(serialize_all = "snake_case")]
+#[repr(u8)]
+pub enum EventCategory {
+    Unknown = 0,
+    NoEvent = 1,
+    GenericOut = 2,
+    Strikeout = 3,
+    StolenBase = 4,
+    DefensiveIndifference = 5,
+    CaughtStealing = 6,
+    /// No longer produced; pickoffs with an error are coded as pickoffs.
+    PickoffError = 7,
+    Pickoff = 8,
+    WildPitch = 9,
+    PassedBall = 10,
+    Balk = 11,
+    OtherAdvance = 12,
+    FoulError = 13,
+    Walk = 14,
+    IntentionalWalk = 15,
+    HitByPitch = 16,
+    Interference = 17,
+    Error = 18,
+    FieldersChoice = 19,
+    Single = 20,
+    Double = 21,
+    Triple = 22,
+    HomeRun = 23,
+    MissingPlay = 24,
+}
+
+impl EventCategory {
+    pub fn from_event(event: &Event) -> Self {
+        Self::from_event_type(&event.event_type)
+    }
+
+    /// The category of the event type. A strikeout or walk with a base running event keeps
+    /// the category of the plate appearance, and a caught stealing or pickoff negated by an
+    /// error keeps its own.
+    pub fn from_event_type(event_type: &EventType) -> Self {
+        match event_type {
+            EventType::Out { .. } | EventType::DoublePlay { .. } | EventType::TriplePlay { .. } => EventCategory::GenericOut,
+            EventType::Strikeout { .. } => EventCategory::Strikeout,
+            EventType::StolenBase { .. } => EventCategory::StolenBase,
+            EventType::DefensiveIndifference => EventCategory::DefensiveIndifference,
+            EventType::CaughtStealing { .. } | EventType::Pickoff { caught_stealing: true, .. } => EventCategory::CaughtStealing,
+            EventType::Pickoff { caught_stealing: false, .. } => EventCategory::Pickoff,
+            EventType::WildPitch => EventCategory::WildPitch,
+            EventType::PassedBall => EventCategory::PassedBall,
+            EventType::Balk => EventCategory::Balk,
+            EventType::OtherAdvance => EventCategory::OtherAdvance,
+            EventType::ErrorOnFoulFlyBall { .. } => EventCategory::FoulError,
+            EventType::Walk { intentional: false, .. } => EventCategory::Walk,
+            EventType::Walk { intentional: true, .. } => EventCategory::IntentionalWalk,
+            EventType::HitByPitch => EventCategory::HitByPitch,
+            EventType::Interference => EventCategory::Interference,
+            EventType::Error { .. } => EventCategory::Error,
+            EventType::FieldersChoice { .. } => EventCategory::FieldersChoice,
+            EventType::Single { .. } => EventCategory::Single,
+            EventType::Double { .. } | EventType::GroundRuleDouble => EventCategory::Double,
+            EventType::Triple { .. } => EventCategory::Triple,
+            EventType::SoloHomeRun | EventType::InsideTheParkHomeRun { .. } => EventCategory::HomeRun,
+            EventType::NoPlay => EventCategory::NoEvent,
+        }
+    }
+
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_code(code: u8) -> Result<Self, String> {
+        let category = match code {
+            0 => EventCategory::Unknown,
+            1 => EventCategory::NoEvent,
+            2 => EventCategory::GenericOut,
+            3 => EventCategory::Strikeout,
+            4 => EventCategory::StolenBase,
+            5 => EventCategory::DefensiveIndifference,
+            6 => EventCategory::CaughtStealing,
+            7 => EventCategory::PickoffError,
+            8 => EventCategory::Pickoff,
+            9 => EventCategory::WildPitch,
+            10 => EventCategory::PassedBall,
+            11 => EventCategory::Balk,
+            12 => EventCategory::OtherAdvance,
+            13 => EventCategory::FoulError,
+            14 => EventCategory::Walk,
+            15 => EventCategory::IntentionalWalk,
+            16 => EventCategory::HitByPitch,
+            17 => EventCategory::Interference,
+            18 => EventCategory::Error,
+            19 => EventCategory::FieldersChoice,
+            20 => EventCategory::Single,
+            21 => EventCategory::Double,
+            22 => EventCategory::Triple,
+            23 => EventCategory::HomeRun,
+            24 => EventCategory::MissingPlay,
+            _ => return Err(format!("Unknown event code: {code}")),
+        };
+
+        Ok(category)
+    }
+
+    pub fn is_hit(&self) -> bool {
+        matches!(self, EventCategory::Single | EventCategory::Double | EventCategory::Triple | EventCategory::HomeRun)
+    }
+}
+
+/// Whether Chadwick would flag the event as a base running play that was negated by an
+/// error, such as `CS2(2E4)` or `PO1(E3)`.
+pub fn is_error_negated(event_type: &EventType) -> bool {
+    match event_type {
+        EventType::CaughtStealing { ball_path, .. } | EventType::Pickoff { ball_path, .. } => ball_path.iter().any(BallPathNode::is_error),
+        _ => false,
+    }
+}
+
+/// The `DAYNIGHT_PARK_CD` code written by `cwgame`.
+pub fn day_night_code(day_night: &DayNightInfo) -> &'static str {
+    match day_night {
+        DayNightInfo::Day => "D",
+        DayNightInfo::Night => "N",
+    }
+}
+
+/// The `PITCHES_RECORD_CD` code written by `cwgame`.
+pub fn pitches_code(pitches: &PitchesInfo) -> u8 {
+    match pitches {
+        PitchesInfo::None => 0,
+        PitchesInfo::Count => 1,
+        PitchesInfo::Pitches => 2,
+    }
+}
+
+/// The `FIELD_PARK_CD` code written by `cwgame`.
+pub fn field_condition_code(field_condition: &FieldConditionInfo) -> u8 {
+    match field_condition {
+        FieldConditionInfo::Unknown => 0,
+        FieldConditionInfo::Soaked => 1,
+        FieldConditionInfo::Wet => 2,
+        FieldConditionInfo::Damp => 3,
+        FieldConditionInfo::Dry => 4,
+    }
+}
+
+/// The `PRECIP_PARK_CD` code written by `cwgame`.
+pub fn precipitation_code(precipitation: &PrecipitationInfo) -> u8 {
+    match precipitation {
+        PrecipitationInfo::Unknown => 0,
+        PrecipitationInfo::None => 1,
+        PrecipitationInfo::Drizzle => 2,
+        PrecipitationInfo::Showers => 3,
+        PrecipitationInfo::Rain => 4,
+        PrecipitationInfo::Snow => 5,
+    }
+}
+
+/// The `SKY_PARK_CD` code written by `cwgame`.
+pub fn sky_code(sky: &SkyInfo) -> u8 {
+    match sky {
+        SkyInfo::Unknown => 0,
+        SkyInfo::Sunny => 1,
+        SkyInfo::Cloudy => 2,
+        SkyInfo::Overcast => 3,
+        SkyInfo::Night => 4,
+        SkyInfo::Dome => 5,
+    }
+}
+
+/// The `WIND_DIRECTION_PARK_CD` code written by `cwgame`.
+pub fn wind_direction_code(wind_direction: &WindDirectionInfo) -> u8 {
+    match wind_direction {
+        WindDirectionInfo::Unknown => 0,
+        WindDirectionInfo::ToLeftField => 1,
+        WindDirectionInfo::ToCenterField => 2,
+        WindDirectionInfo::ToRightField => 3,
+        WindDirectionInfo::LeftToRight => 4,
+        WindDirectionInfo::FromLeftField => 5,
+        WindDirectionInfo::FromCenterField => 6,
+        WindDirectionInfo::FromRightField => 7,
+        WindDirectionInfo::RightToLeft => 8,
+    }
+}
+
+/// The one letter `GAME_TYPE_CD` code, as used in MLB's schedule data.
+pub fn game_type_code(game_type: &GameTypeInfo) -> &'static str {
+    match game_type {
+        GameTypeInfo::Regular => "R",
+        GameTypeInfo::Exhibition => "E",
+        GameTypeInfo::Preseason => "S",
+        GameTypeInfo::AllStar => "A",
+        GameTypeInfo::Playoff => "P",
+        GameTypeInfo::WorldSeries => "W",
+        GameTypeInfo::LeagueChampionshipSeries => "L",
+        GameTypeInfo::DivisionSeries => "D",
+        GameTypeInfo::Wildcard => "F",
+        GameTypeInfo::Championship => "C",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::game::{Base, BattedBall, EventModifier, Fielder, FieldingErrorType, Runner, Trajectory};
+
+    fn event(event_type: EventType) -> Event {
+        Event {
+            event_type,
+            modifiers: Vec::new(),
+            advances: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for code in 0..=24 {
+            assert_eq!(EventCategory::from_code(code).unwrap().code(), code);
+        }
+
+        assert!(EventCategory::from_code(25).is_err());
+    }
+
+    #[test]
+    fn categorize_events() {
+        let cases = vec![
+            (EventType::Out { credited_fielder: Fielder::Known(3), assisting_fielders: vec![Fielder::Known(6)], runner_out: Runner::Batter }, 2),
+            (EventType::DoublePlay { credited_fielders: vec![Fielder::Known(4), Fielder::Known(3)], assisting_fielders: vec![Fielder::Known(6)], runners_out: vec![Runner::First, Runner::Batter] }, 2),
+            (EventType::Strikeout { ball_path: Vec::new(), base_running_event: Box::new(Some(EventType::StolenBase { bases: vec![Base::Second] })) }, 3),
+            (EventType::StolenBase { bases: vec![Base::Second] }, 4),
+            (EventType::DefensiveIndifference, 5),
+            (EventType::CaughtStealing { base: Base::Second, ball_path: Vec::new() }, 6),
+            (EventType::Pickoff { caught_stealing: true, base: Base::Second, ball_path: Vec::new() }, 6),
+            (EventType::Pickoff { caught_stealing: false, base: Base::First, ball_path: Vec::new() }, 8),
+            (EventType::WildPitch, 9),
+            (EventType::PassedBall, 10),
+            (EventType::Balk, 11),
+            (EventType::OtherAdvance, 12),
+            (EventType::ErrorOnFoulFlyBall { credited_fielder: Fielder::Known(5) }, 13),
+            (EventType::Walk { intentional: false, base_running_event: Box::new(None) }, 14),
+            (EventType::Walk { intentional: true, base_running_event: Box::new(None) }, 15),
+            (EventType::HitByPitch, 16),
+            (EventType::Interference, 17),
+            (EventType::Error { credited_fielder: Fielder::Known(6), assisting_fielders: Vec::new() }, 18),
+            (EventType::FieldersChoice { credited_fielder: Fielder::Known(6) }, 19),
+            (EventType::Single { credited_fielder: Fielder::Known(7), assisting_fielders: Vec::new() }, 20),
+            (EventType::GroundRuleDouble, 21),
+            (EventType::Triple { credited_fielder: Fielder::Known(9), assisting_fielders: Vec::new() }, 22),
+            (EventType::InsideTheParkHomeRun { credited_fielder: Fielder::Known(8) }, 23),
+            (EventType::SoloHomeRun, 23),
+            (EventType::NoPlay, 1),
+        ];
+
+        for (event_type, code) in cases {
+            assert_eq!(EventCategory::from_event(&event(event_type.clone())).code(), code, "{event_type:?}");
+        }
+    }
+
+    #[test]
+    fn sacrifices_keep_their_category() {
+        let sacrifice_bunt = Event {
+            modifiers: vec![
+                EventModifier::SacrificeHitOrBunt,
+                EventModifier::BattedBall(BattedBall { trajectory: Some(Trajectory::GroundBallBunt), location: None }),
+            ],
+            ..event(EventType::Out { credited_fielder: Fielder::Known(3), assisting_fielders: vec![Fielder::Known(1)], runner_out: Runner::Batter })
+        };
+        assert_eq!(EventCategory::from_event(&sacrifice_bunt), EventCategory::GenericOut);
+    }
+
+    #[test]
+    fn error_negated_base_running() {
+        let caught_stealing = EventType::CaughtStealing {
+            base: Base::Second,
+            ball_path: vec![
+                BallPathNode::Success { fielder: Fielder::Known(2) },
+                BallPathNode::Error { fielder: Fielder::Known(4), error_type: FieldingErrorType::NonThrowingError },
+            ],
+        };
+        assert!(is_error_negated(&caught_stealing));
+        assert_eq!(EventCategory::from_event_type(&caught_stealing), EventCategory::CaughtStealing);
+        assert!(!is_error_negated(&EventType::CaughtStealing { base: Base::Second, ball_path: Vec::new() }));
+    }
+
+    #[test]
+    fn info_codes() {
+        assert_eq!(day_night_code(&DayNightInfo::Night), "N");
+        assert_eq!(pitches_code(&PitchesInfo::Count), 1);
+        assert_eq!(field_condition_code(&FieldConditionInfo::Damp), 3);
+        assert_eq!(precipitation_code(&PrecipitationInfo::Snow), 5);
+        assert_eq!(sky_code(&SkyInfo::Night), 4);
+        assert_eq!(wind_direction_code(&WindDirectionInfo::RightToLeft), 8);
+        assert_eq!(game_type_code(&GameTypeInfo::WorldSeries), "W");
+    }
+}
@@ -0,0 +1,509 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::game::{AdvanceParameter, BallPathNode, Base, Event, EventType, Game, Play, RunnerAdvance, Team};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PitcherEarnedRuns {
+    pub pitcher_id: String,
+    /// The fielding team.
+    pub team: Team,
+    pub runs: u8,
+    pub earned_runs: u8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TeamEarnedRuns {
+    /// The fielding team.
+    pub team: Team,
+    pub runs: u8,
+    pub earned_runs: u8,
+}
+
+/// A pitcher whose computed earned runs disagree with the file's `data,er` record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EarnedRunMismatch {
+    pub pitcher_id: String,
+    pub computed: u8,
+    pub recorded: u8,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EarnedRunReport {
+    pub pitchers: Vec<PitcherEarnedRuns>,
+    pub teams: Vec<TeamEarnedRuns>,
+    pub mismatches: Vec<EarnedRunMismatch>,
+}
+
+impl EarnedRunReport {
+    pub fn get_pitcher(&self, pitcher_id: &str) -> Option<&PitcherEarnedRuns> {
+        self.pitchers.iter().find(|p| p.pitcher_id == pitcher_id)
+    }
+
+    pub fn get_team(&self, team: &Team) -> Option<&TeamEarnedRuns> {
+        self.teams.iter().find(|t| &t.team == team)
+    }
+}
+
+/// Whether a run counts as earned against the pitcher charged with it and against the team.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunEarned {
+    pub pitcher: bool,
+    pub team: bool,
+}
+
+/// The state of a half inning as it would have played out without errors and passed balls.
+#[derive(Default)]
+struct Reconstruction {
+    /// Outs made plus outs that errors took away.
+    outs: u8,
+    /// The same count for each pitcher, starting from the actual outs when they entered, as
+    /// a reliever doesn't benefit from chances for outs missed before they came in.
+    pitcher_outs: HashMap<String, u8>,
+    /// Runners who reached on an error, interference or a muffed foul fly.
+    unearned_runners: HashSet<String>,
+    /// A batter whose time at bat was extended by an error on a foul fly.
+    extended_batter: Option<String>,
+    /// For the runner on each base, the base they would be on without errors and passed
+    /// balls, counting first as 1. `None` if they wouldn't be on base at all.
+    bases: [Option<u8>; 3],
+}
+
+impl Reconstruction {
+    fn play(&mut self, play: &Play) -> Vec<(RunnerAdvance, RunEarned)> {
+        if let Some(pitcher_id) = play.get_pitcher_id() {
+            self.pitcher_outs.entry(pitcher_id.to_string()).or_insert(play.get_outs());
+        }
+
+        let Some(event) = play.get_event() else {
+            return Vec::new();
+        };
+
+        let batter = play.get_runner_advances()
+            .iter()
+            .find(|ra| ra.advance.starting_base == Base::Home);
+        let batter_safe = batter.is_some_and(|ra| !ra.advance.out);
+
+        let mut missed_outs = event.advances
+            .iter()
+            .filter(|advance| advance.out && !advance.is_out())
+            .count() as u8;
+        missed_outs += missed_outs_on_event(&event.event_type);
+
+        if batter_safe && batter_reached_unearned(&event.event_type) {
+            self.unearned_runners.insert(play.get_batter_id().to_string());
+        }
+
+        if let EventType::ErrorOnFoulFlyBall { .. } = event.event_type {
+            self.extended_batter = Some(play.get_batter_id().to_string());
+        } else if self.extended_batter.take().as_ref() == Some(play.get_batter_id()) && batter_safe {
+            missed_outs += 1;
+            self.unearned_runners.insert(play.get_batter_id().to_string());
+        }
+
+        let reconstructed = self.advance(play, event);
+
+        let mut runs = Vec::new();
+        for (runner_advance, position) in play.get_runner_advances().iter().zip(&reconstructed) {
+            if !runner_advance.advance.is_run() {
+                continue;
+            }
+
+            let parameters = &runner_advance.advance.parameters;
+            let pitcher_outs = runner_advance.runner.pitcher_id
+                .as_ref()
+                .and_then(|id| self.pitcher_outs.get(id))
+                .copied()
+                .unwrap_or(self.outs);
+
+            let run_earned = if parameters.contains(&AdvanceParameter::Unearned) {
+                RunEarned { pitcher: false, team: false }
+            } else if parameters.contains(&AdvanceParameter::TeamUnearned) {
+                RunEarned { pitcher: true, team: false }
+            } else {
+                let aided = self.unearned_runners.contains(&runner_advance.runner.id) || *position != Some(HOME);
+
+                RunEarned {
+                    pitcher: !aided && pitcher_outs + missed_outs < 3,
+                    team: !aided && self.outs + missed_outs < 3,
+                }
+            };
+
+            runs.push((runner_advance.clone(), run_earned));
+        }
+
+        let outs = play.get_runner_advances().iter().filter(|ra| ra.advance.out).count() as u8;
+        self.outs += missed_outs + outs;
+        for pitcher_outs in self.pitcher_outs.values_mut() {
+            *pitcher_outs += missed_outs + outs;
+        }
+
+        runs
+    }
+
+    /// Moves the reconstructed runners along with the play, returning where each runner
+    /// advance ends up in the reconstruction. Runners only keep the bases they gained
+    /// without the help of an error or passed ball, take at least as many bases as the
+    /// hit, and are pushed ahead by the runners behind them. Runners who reached on an
+    /// error or were safe on one are left out.
+    fn advance(&mut self, play: &Play, event: &Event) -> Vec<Option<u8>> {
+        let runner_advances = play.get_runner_advances();
+
+        // (runner advance, actual base, reconstructed base), with runners who stayed put
+        let mut runners = Vec::new();
+        for (index, runner) in play.get_bases().iter().enumerate() {
+            let moved = runner_advances.iter().any(|ra| ra.advance.starting_base.index() == Some(index));
+            if runner.is_some() && !moved {
+                runners.push((None, index as u8 + 1, self.bases[index]));
+            }
+        }
+
+        for (position, runner_advance) in runner_advances.iter().enumerate() {
+            let advance = &runner_advance.advance;
+            if advance.out {
+                continue;
+            }
+
+            let start = match advance.starting_base.index() {
+                Some(index) => self.bases[index],
+                None => Some(0),
+            };
+            let safe_on_error = event.advances
+                .iter()
+                .any(|a| a.starting_base == advance.starting_base && a.out && !a.is_out());
+
+            let reconstructed = start
+                .filter(|_| !safe_on_error && !self.unearned_runners.contains(&runner_advance.runner.id))
+                .map(|start| {
+                    let gained = if !advanced_on_error(runner_advance, &event.event_type) {
+                        (end_position(&advance.ending_base) - start_position(&advance.starting_base)).max(hit_bases(&event.event_type))
+                    } else if advance.starting_base == Base::Home {
+                        bases_awarded(&event.event_type)
+                    } else {
+                        0
+                    };
+
+                    (start + gained).min(HOME)
+                });
+
+            runners.push((Some(position), end_position(&advance.ending_base), reconstructed));
+        }
+
+        runners.sort_by_key(|(_, actual, _)| *actual);
+
+        let mut behind = 0;
+        for (_, _, reconstructed) in runners.iter_mut() {
+            if let Some(base) = reconstructed {
+                if *base <= behind && behind < HOME {
+                    *base = behind + 1;
+                }
+                behind = *base;
+            }
+        }
+
+        self.bases = [None; 3];
+        let mut positions = vec![None; runner_advances.len()];
+        for (position, actual, reconstructed) in runners {
+            if actual < HOME {
+                self.bases[actual as usize - 1] = reconstructed;
+            }
+            if let Some(position) = position {
+                positions[position] = reconstructed;
+            }
+        }
+
+        positions
+    }
+}
+
+/// Home as a reconstructed base, counting first as 1.
+const HOME: u8 = 4;
+
+fn start_position(base: &Base) -> u8 {
+    base.index().map(|index| index as u8 + 1).unwrap_or(0)
+}
+
+fn end_position(base: &Base) -> u8 {
+    base.index().map(|index| index as u8 + 1).unwrap_or(HOME)
+}
+
+/// Bases every runner takes on a hit.
+fn hit_bases(event_type: &EventType) -> u8 {
+    match event_type {
+        EventType::Single { .. } => 1,
+        EventType::Double { .. } | EventType::GroundRuleDouble => 2,
+        EventType::Triple { .. } => 3,
+        EventType::SoloHomeRun | EventType::InsideTheParkHomeRun { .. } => HOME,
+        _ => 0,
+    }
+}
+
+/// Bases the batter earns on the event, whatever an error adds to them.
+fn bases_awarded(event_type: &EventType) -> u8 {
+    match event_type {
+        EventType::Walk { .. } | EventType::HitByPitch => 1,
+        _ => hit_bases(event_type),
+    }
+}
+
+fn has_error(ball_path: &[BallPathNode]) -> bool {
+    ball_path.iter().any(BallPathNode::is_error)
+}
+
+/// Outs the defence would have made on the event but for an error.
+fn missed_outs_on_event(event_type: &EventType) -> u8 {
+    match event_type {
+        EventType::Error { .. } => 1,
+        EventType::CaughtStealing { ball_path, .. } | EventType::Pickoff { ball_path, .. } => has_error(ball_path) as u8,
+        EventType::Strikeout { base_running_event, .. } => match base_running_event.as_ref() {
+            Some(EventType::PassedBall) | Some(EventType::Error { .. }) => 1,
+            Some(base_running_event) => missed_outs_on_event(base_running_event),
+            None => 0,
+        },
+        EventType::Walk { base_running_event, .. } => base_running_event
+            .as_ref()
+            .as_ref()
+            .map(missed_outs_on_event)
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Whether a batter reaching base on the event does so without earning it.
+fn batter_reached_unearned(event_type: &EventType) -> bool {
+    match event_type {
+        EventType::Error { .. } | EventType::Interference => true,
+        EventType::Strikeout { base_running_event, .. } => matches!(
+            base_running_event.as_ref(),
+            Some(EventType::PassedBall) | Some(EventType::Error { .. }),
+        ),
+        _ => false,
+    }
+}
+
+fn is_passed_ball(event_type: &EventType) -> bool {
+    match event_type {
+        EventType::PassedBall => true,
+        EventType::Strikeout { base_running_event, .. } | EventType::Walk { base_running_event, .. } => {
+            base_running_event.as_ref().as_ref().is_some_and(is_passed_ball)
+        },
+        _ => false,
+    }
+}
+
+/// Whether the event itself is an error, including one on a base running play.
+fn is_error_event(event_type: &EventType) -> bool {
+    match event_type {
+        EventType::Error { .. } => true,
+        EventType::CaughtStealing { ball_path, .. } | EventType::Pickoff { ball_path, .. } => has_error(ball_path),
+        EventType::Strikeout { base_running_event, .. } | EventType::Walk { base_running_event, .. } => {
+            base_running_event.as_ref().as_ref().is_some_and(is_error_event)
+        },
+        _ => false,
+    }
+}
+
+fn advanced_on_error(runner_advance: &RunnerAdvance, event_type: &EventType) -> bool {
+    let on_error = runner_advance.advance.parameters.iter().any(|parameter| match parameter {
+        AdvanceParameter::BallPath(ball_path) => has_error(ball_path),
+        AdvanceParameter::PassedBall => true,
+        _ => false,
+    });
+
+    on_error || is_passed_ball(event_type) || is_error_event(event_type)
+}
+
+/// Decides, for every run scored in the game, whether it was earned. Each half inning is
+/// reconstructed as if errors and passed balls hadn't happened: runners who reached on
+/// errors are unearned, as are runs by runners who wouldn't have reached home without the
+/// bases errors and passed balls gave them, and runs after the reconstructed third out.
+/// Explicit `(UR)` and `(TUR)` markers always win.
+pub fn classify_runs(game: &Game) -> Vec<(RunnerAdvance, Team, RunEarned)> {
+    let mut runs = Vec::new();
+    let mut half_inning = None;
+    let mut reconstruction = Reconstruction::default();
+
+    for play in game.get_plays() {
+        if half_inning != Some((play.get_inning(), play.get_team())) {
+            half_inning = Some((play.get_inning(), play.get_team()));
+            reconstruction = Reconstruction::default();
+        }
+
+        let fielding_team = play.get_team().opponent();
+        for (runner_advance, run_earned) in reconstruction.play(play) {
+            runs.push((runner_advance, fielding_team, run_earned));
+        }
+    }
+
+    runs
+}
+
+/// Totals runs and earned runs by pitcher and team, and checks them against the file's
+/// `data,er` records.
+pub fn compute_earned_runs(game: &Game) -> EarnedRunReport {
+    let mut report = EarnedRunReport::default();
+
+    for play in game.get_plays() {
+        let team = play.get_team().opponent();
+
+        if let Some(pitcher_id) = play.get_pitcher_id()
+            && report.get_pitcher(pitcher_id).is_none()
+        {
+            report.pitchers.push(PitcherEarnedRuns {
+                pitcher_id: pitcher_id.to_string(),
+                team,
+                runs: 0,
+                earned_runs: 0,
+            });
+        }
+
+        if report.get_team(&team).is_none() {
+            report.teams.push(TeamEarnedRuns {
+                team,
+                runs: 0,
+                earned_runs: 0,
+            });
+        }
+    }
+
+    for (runner_advance, team, run_earned) in classify_runs(game) {
+        let pitcher = report.pitchers
+            .iter_mut()
+            .find(|p| Some(&p.pitcher_id) == runner_advance.runner.pitcher_id.as_ref());
+
+        if let Some(pitcher) = pitcher {
+            pitcher.runs += 1;
+            pitcher.earned_runs += run_earned.pitcher as u8;
+        }
+
+        if let Some(team_earned_runs) = report.teams.iter_mut().find(|t| t.team == team) {
+            team_earned_runs.runs += 1;
+            team_earned_runs.earned_runs += run_earned.team as u8;
+        }
+    }
+
+    for (pitcher_id, recorded) in game.get_earned_runs() {
+        let computed = report.get_pitcher(pitcher_id).map(|p| p.earned_runs).unwrap_or(0);
+
+        if computed != *recorded {
+            report.mismatches.push(EarnedRunMismatch {
+                pitcher_id: pitcher_id.to_string(),
+                computed,
+                recorded: *recorded,
+            });
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::FileParser;
+
+    fn parse(plays: &str) -> Game {
+        let file = format!("id,CIN199004090
+version,2
+info,visteam,HOU
+info,hometeam,CIN
+start,youngg001,\"Gerald Young\",0,1,8
+start,biggc001,\"Craig Biggio\",0,2,2
+start,davig001,\"Glenn Davis\",0,3,3
+start,doraw001,\"Bill Doran\",0,4,4
+start,rijoj001,\"Jose Rijo\",1,0,1
+{plays}");
+
+        FileParser::new().parse_file(&file).unwrap().remove(0)
+    }
+
+    #[test]
+    fn earned_runs_without_errors() {
+        let game = parse("play,1,0,youngg001,00,X,S7
+play,1,0,biggc001,00,X,HR.1-H
+data,er,rijoj001,2");
+
+        let report = compute_earned_runs(&game);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().earned_runs, 2);
+        assert_eq!(report.get_team(&Team::Home).unwrap().earned_runs, 2);
+        assert!(report.mismatches.is_empty());
+    }
+
+    #[test]
+    fn runner_reaching_on_error_is_unearned() {
+        let game = parse("play,1,0,youngg001,00,X,E6
+play,1,0,biggc001,00,X,HR.1-H
+data,er,rijoj001,2");
+
+        let report = compute_earned_runs(&game);
+        let pitcher = report.get_pitcher("rijoj001").unwrap();
+        assert_eq!(pitcher.runs, 2);
+        assert_eq!(pitcher.earned_runs, 1);
+        assert_eq!(report.mismatches, vec![EarnedRunMismatch {
+            pitcher_id: "rijoj001".to_string(),
+            computed: 1,
+            recorded: 2,
+        }]);
+    }
+
+    #[test]
+    fn runs_after_reconstructed_third_out_are_unearned() {
+        let game = parse("play,1,0,youngg001,00,X,8/F
+play,1,0,biggc001,00,X,63/G
+play,1,0,davig001,00,X,E6
+play,1,0,doraw001,00,X,HR.1-H");
+
+        let report = compute_earned_runs(&game);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().runs, 2);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().earned_runs, 0);
+    }
+
+    #[test]
+    fn reliever_does_not_benefit_from_earlier_errors() {
+        let game = parse("play,1,0,youngg001,00,X,8/F
+play,1,0,biggc001,00,X,E6
+sub,myerr001,\"Randy Myers\",1,0,1
+play,1,0,davig001,00,X,63/G.1-2
+play,1,0,doraw001,00,X,HR.2-H");
+
+        let report = compute_earned_runs(&game);
+        // biggio reached on an error, and the inning would have been over for the team
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().earned_runs, 0);
+        assert_eq!(report.get_pitcher("myerr001").unwrap().earned_runs, 1);
+        assert_eq!(report.get_team(&Team::Home).unwrap().runs, 2);
+        assert_eq!(report.get_team(&Team::Home).unwrap().earned_runs, 0);
+    }
+
+    #[test]
+    fn explicit_unearned_marker() {
+        let game = parse("play,1,0,youngg001,00,X,D7
+play,1,0,biggc001,00,X,S8.2-H(UR)");
+
+        let report = compute_earned_runs(&game);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().runs, 1);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().earned_runs, 0);
+    }
+
+    #[test]
+    fn bases_taken_on_an_error_are_given_back() {
+        let game = parse("play,1,0,youngg001,00,X,S7
+play,1,0,biggc001,00,X,S9.1-3(E9/TH)
+play,1,0,davig001,00,X,9/SF.3-H");
+
+        // young would only have been on second, so the sacrifice fly wouldn't score him
+        let report = compute_earned_runs(&game);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().runs, 1);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().earned_runs, 0);
+    }
+
+    #[test]
+    fn runners_keep_the_bases_a_hit_gives_them() {
+        let game = parse("play,1,0,youngg001,00,X,S7
+play,1,0,biggc001,00,X,S9.1-3(E9/TH)
+play,1,0,davig001,00,X,D8.3-H;1-3");
+
+        // a double would have scored young from second
+        let report = compute_earned_runs(&game);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().runs, 1);
+        assert_eq!(report.get_pitcher("rijoj001").unwrap().earned_runs, 1);
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(feature = "arrow")]
+pub mod columnar;
+pub mod cwevent;
+pub mod cwgame;
+#[cfg(feature = "serde")]
+pub mod jsonl;
+
+use std::io::Write;
+
+/// Chadwick's spelling of a flag.
+fn flag(value: bool) -> String {
+    if value { "T" } else { "F" }.to_string()
+}
+
+pub(crate) fn csv_writer<W: Write>(writer: W) -> csv::Writer<W> {
+    csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Necessary)
+        .from_writer(writer)
+}
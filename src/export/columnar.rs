@@ -0,0 +1,369 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BooleanBuilder, RecordBatch, StringBuilder, StringDictionaryBuilder, UInt8Builder, UInt16Builder, UInt32Builder};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+
+use crate::chadwick::EventCategory;
+use crate::parser::game::{AttendanceInfo, Base, Count, Event, Game, Team, TemperatureInfo, TimeOfGameInfo};
+
+fn dictionary() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+/// One row per play, with the state before the play. Ids, teams and event categories are
+/// dictionary encoded, since they repeat across millions of rows.
+pub fn plays_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("game_id", dictionary(), false),
+        Field::new("play_index", DataType::UInt32, false),
+        Field::new("inning", DataType::UInt8, false),
+        Field::new("batting_team_id", dictionary(), true),
+        Field::new("is_home_batting", DataType::Boolean, false),
+        Field::new("outs", DataType::UInt8, false),
+        Field::new("balls", DataType::UInt8, true),
+        Field::new("strikes", DataType::UInt8, true),
+        Field::new("pitches", DataType::Utf8, true),
+        Field::new("away_score", DataType::UInt8, false),
+        Field::new("home_score", DataType::UInt8, false),
+        Field::new("batter_id", dictionary(), false),
+        Field::new("pitcher_id", dictionary(), true),
+        Field::new("first_runner_id", dictionary(), true),
+        Field::new("second_runner_id", dictionary(), true),
+        Field::new("third_runner_id", dictionary(), true),
+        Field::new("event_text", DataType::Utf8, false),
+        Field::new("event_category", dictionary(), false),
+        Field::new("is_at_bat", DataType::Boolean, false),
+        Field::new("outs_on_play", DataType::UInt8, false),
+        Field::new("runs", DataType::UInt8, false),
+        Field::new("rbi", DataType::UInt8, false),
+    ]))
+}
+
+/// One row per game, with the info records and the final score.
+pub fn games_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("game_id", DataType::Utf8, false),
+        Field::new("date", DataType::Utf8, true),
+        Field::new("game_number", DataType::UInt8, true),
+        Field::new("visiting_team_id", dictionary(), true),
+        Field::new("home_team_id", dictionary(), true),
+        Field::new("site", dictionary(), true),
+        Field::new("day_night", dictionary(), true),
+        Field::new("used_dh", DataType::Boolean, true),
+        Field::new("temperature", DataType::UInt8, true),
+        Field::new("time_of_game", DataType::UInt16, true),
+        Field::new("attendance", DataType::UInt32, true),
+        Field::new("winning_pitcher_id", dictionary(), true),
+        Field::new("losing_pitcher_id", dictionary(), true),
+        Field::new("save_pitcher_id", dictionary(), true),
+        Field::new("game_type", dictionary(), true),
+        Field::new("innings", DataType::UInt8, false),
+        Field::new("visiting_score", DataType::UInt8, false),
+        Field::new("home_score", DataType::UInt8, false),
+    ]))
+}
+
+type DictionaryBuilder = StringDictionaryBuilder<Int32Type>;
+
+const GAMES_PER_BATCH: usize = 100;
+
+/// Converts games into Arrow record batches, and writes those as Parquet. Parquet is written
+/// through one writer a batch of games at a time, so the whole input is never held as Arrow
+/// arrays at once.
+pub struct ColumnarExporter {
+    games_per_batch: usize,
+}
+
+impl Default for ColumnarExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColumnarExporter {
+    pub fn new() -> Self {
+        Self::with_games_per_batch(GAMES_PER_BATCH)
+    }
+
+    pub fn with_games_per_batch(games_per_batch: usize) -> Self {
+        Self {
+            games_per_batch: games_per_batch.max(1),
+        }
+    }
+
+    pub fn plays_batch<'a>(&self, games: impl IntoIterator<Item = &'a Game>) -> Result<RecordBatch, String> {
+        let mut game_id = DictionaryBuilder::new();
+        let mut play_index = UInt32Builder::new();
+        let mut inning = UInt8Builder::new();
+        let mut batting_team_id = DictionaryBuilder::new();
+        let mut is_home_batting = BooleanBuilder::new();
+        let mut outs = UInt8Builder::new();
+        let mut balls = UInt8Builder::new();
+        let mut strikes = UInt8Builder::new();
+        let mut pitches = StringBuilder::new();
+        let mut away_score = UInt8Builder::new();
+        let mut home_score = UInt8Builder::new();
+        let mut batter_id = DictionaryBuilder::new();
+        let mut pitcher_id = DictionaryBuilder::new();
+        let mut runner_ids = [DictionaryBuilder::new(), DictionaryBuilder::new(), DictionaryBuilder::new()];
+        let mut event_text = StringBuilder::new();
+        let mut event_category = DictionaryBuilder::new();
+        let mut is_at_bat = BooleanBuilder::new();
+        let mut outs_on_play = UInt8Builder::new();
+        let mut runs = UInt8Builder::new();
+        let mut rbi = UInt8Builder::new();
+
+        for game in games {
+            let info = game.get_info_state();
+            let (mut away, mut home) = (0, 0);
+
+            for (index, play) in game.get_plays().iter().enumerate() {
+                game_id.append_value(game.get_id());
+                play_index.append_value(index as u32);
+                inning.append_value(play.get_inning());
+                batting_team_id.append_option(match play.get_team() {
+                    Team::Visiting => info.visiting_team.as_deref(),
+                    Team::Home => info.home_team.as_deref(),
+                });
+                is_home_batting.append_value(play.get_team() == Team::Home);
+                outs.append_value(play.get_outs());
+
+                match play.get_count() {
+                    Some(Count::Known { balls: ball_count, strikes: strike_count }) => {
+                        balls.append_value(*ball_count);
+                        strikes.append_value(*strike_count);
+                    },
+                    _ => {
+                        balls.append_null();
+                        strikes.append_null();
+                    },
+                }
+
+                pitches.append_option(play.get_pitches().map(|pitches| pitches.iter().map(|pitch| pitch.to_string()).collect::<String>()));
+                away_score.append_value(away);
+                home_score.append_value(home);
+                batter_id.append_value(play.get_batter_id());
+                pitcher_id.append_option(play.get_pitcher_id());
+
+                for (runner_id, base) in runner_ids.iter_mut().zip([Base::First, Base::Second, Base::Third]) {
+                    runner_id.append_option(play.get_runner(&base).map(|runner| runner.id.as_str()));
+                }
+
+                let event = play.get_event();
+                event_text.append_value(play.get_event_text());
+                event_category.append_value(event.map(EventCategory::from_event).unwrap_or(EventCategory::Unknown).to_string());
+                is_at_bat.append_value(event.is_some_and(|event| event.is_at_bat()));
+                outs_on_play.append_value(event.map_or(0, Event::outs_recorded));
+                runs.append_value(play.get_runs());
+                rbi.append_value(play.get_rbi());
+
+                match play.get_team() {
+                    Team::Visiting => away += play.get_runs(),
+                    Team::Home => home += play.get_runs(),
+                }
+            }
+        }
+
+        let [mut first_runner_id, mut second_runner_id, mut third_runner_id] = runner_ids;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(game_id.finish()),
+            Arc::new(play_index.finish()),
+            Arc::new(inning.finish()),
+            Arc::new(batting_team_id.finish()),
+            Arc::new(is_home_batting.finish()),
+            Arc::new(outs.finish()),
+            Arc::new(balls.finish()),
+            Arc::new(strikes.finish()),
+            Arc::new(pitches.finish()),
+            Arc::new(away_score.finish()),
+            Arc::new(home_score.finish()),
+            Arc::new(batter_id.finish()),
+            Arc::new(pitcher_id.finish()),
+            Arc::new(first_runner_id.finish()),
+            Arc::new(second_runner_id.finish()),
+            Arc::new(third_runner_id.finish()),
+            Arc::new(event_text.finish()),
+            Arc::new(event_category.finish()),
+            Arc::new(is_at_bat.finish()),
+            Arc::new(outs_on_play.finish()),
+            Arc::new(runs.finish()),
+            Arc::new(rbi.finish()),
+        ];
+
+        RecordBatch::try_new(plays_schema(), columns).map_err(|e| e.to_string())
+    }
+
+    pub fn games_batch<'a>(&self, games: impl IntoIterator<Item = &'a Game>) -> Result<RecordBatch, String> {
+        let mut game_id = StringBuilder::new();
+        let mut date = StringBuilder::new();
+        let mut game_number = UInt8Builder::new();
+        let mut visiting_team_id = DictionaryBuilder::new();
+        let mut home_team_id = DictionaryBuilder::new();
+        let mut site = DictionaryBuilder::new();
+        let mut day_night = DictionaryBuilder::new();
+        let mut used_dh = BooleanBuilder::new();
+        let mut temperature = UInt8Builder::new();
+        let mut time_of_game = UInt16Builder::new();
+        let mut attendance = UInt32Builder::new();
+        let mut winning_pitcher_id = DictionaryBuilder::new();
+        let mut losing_pitcher_id = DictionaryBuilder::new();
+        let mut save_pitcher_id = DictionaryBuilder::new();
+        let mut game_type = DictionaryBuilder::new();
+        let mut innings = UInt8Builder::new();
+        let mut visiting_score = UInt8Builder::new();
+        let mut home_score = UInt8Builder::new();
+
+        for game in games {
+            let info = game.get_info_state();
+            let line_score = game.get_line_score();
+
+            game_id.append_value(game.get_id());
+            date.append_option(info.date.as_deref());
+            game_number.append_option(info.number);
+            visiting_team_id.append_option(info.visiting_team.as_deref());
+            home_team_id.append_option(info.home_team.as_deref());
+            site.append_option(info.site.as_deref());
+            day_night.append_option(info.day_night.map(|day_night| day_night.to_string()));
+            used_dh.append_option(info.used_designated_hitter_rule);
+            temperature.append_option(match info.temperature {
+                Some(TemperatureInfo::Known(temperature)) => Some(temperature),
+                _ => None,
+            });
+            time_of_game.append_option(match info.time_of_game {
+                Some(TimeOfGameInfo::Known(minutes)) => Some(minutes),
+                _ => None,
+            });
+            attendance.append_option(match info.attendance {
+                Some(AttendanceInfo::Known(attendance)) => Some(attendance),
+                _ => None,
+            });
+            winning_pitcher_id.append_option(info.wp.as_deref());
+            losing_pitcher_id.append_option(info.lp.as_deref());
+            save_pitcher_id.append_option(info.save.as_deref());
+            game_type.append_option(info.game_type.map(|game_type| game_type.to_string()));
+            innings.append_value(line_score.visiting.len().max(line_score.home.len()) as u8);
+            visiting_score.append_value(line_score.get_runs(&Team::Visiting));
+            home_score.append_value(line_score.get_runs(&Team::Home));
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(game_id.finish()),
+            Arc::new(date.finish()),
+            Arc::new(game_number.finish()),
+            Arc::new(visiting_team_id.finish()),
+            Arc::new(home_team_id.finish()),
+            Arc::new(site.finish()),
+            Arc::new(day_night.finish()),
+            Arc::new(used_dh.finish()),
+            Arc::new(temperature.finish()),
+            Arc::new(time_of_game.finish()),
+            Arc::new(attendance.finish()),
+            Arc::new(winning_pitcher_id.finish()),
+            Arc::new(losing_pitcher_id.finish()),
+            Arc::new(save_pitcher_id.finish()),
+            Arc::new(game_type.finish()),
+            Arc::new(innings.finish()),
+            Arc::new(visiting_score.finish()),
+            Arc::new(home_score.finish()),
+        ];
+
+        RecordBatch::try_new(games_schema(), columns).map_err(|e| e.to_string())
+    }
+
+    pub fn write_plays_parquet<'a, W: Write + Send>(&self, games: impl IntoIterator<Item = &'a Game>, writer: W) -> Result<(), String> {
+        self.write_parquet(plays_schema(), games, |chunk| self.plays_batch(chunk.iter().copied()), writer)
+    }
+
+    pub fn write_games_parquet<'a, W: Write + Send>(&self, games: impl IntoIterator<Item = &'a Game>, writer: W) -> Result<(), String> {
+        self.write_parquet(games_schema(), games, |chunk| self.games_batch(chunk.iter().copied()), writer)
+    }
+
+    fn write_parquet<'a, W: Write + Send>(
+        &self,
+        schema: SchemaRef,
+        games: impl IntoIterator<Item = &'a Game>,
+        to_batch: impl Fn(&[&'a Game]) -> Result<RecordBatch, String>,
+        writer: W,
+    ) -> Result<(), String> {
+        let mut writer = ArrowWriter::try_new(writer, schema, None).map_err(|e| e.to_string())?;
+        let mut chunk = Vec::with_capacity(self.games_per_batch);
+
+        for game in games {
+            chunk.push(game);
+            if chunk.len() == self.games_per_batch {
+                writer.write(&to_batch(&chunk)?).map_err(|e| e.to_string())?;
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            writer.write(&to_batch(&chunk)?).map_err(|e| e.to_string())?;
+        }
+
+        writer.close().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use arrow::array::{Array, AsArray, DictionaryArray, StringArray};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn games() -> Vec<Game> {
+        fixtures::parse(fixtures::COLUMNAR_FILE)
+    }
+
+    fn dictionary_values(batch: &RecordBatch, column: &str) -> Vec<Option<String>> {
+        let array = batch.column_by_name(column).unwrap().as_any().downcast_ref::<DictionaryArray<Int32Type>>().unwrap();
+        let values = array.values().as_any().downcast_ref::<StringArray>().unwrap();
+        array.keys().iter().map(|key| key.map(|key| values.value(key as usize).to_string())).collect()
+    }
+
+    #[test]
+    fn plays_batch() {
+        let batch = ColumnarExporter::new().plays_batch(&games()).unwrap();
+
+        assert_eq!(batch.num_rows(), 5);
+        assert_eq!(batch.schema(), plays_schema());
+        assert_eq!(dictionary_values(&batch, "batting_team_id")[4], Some("ANA".to_string()));
+        assert_eq!(dictionary_values(&batch, "second_runner_id")[2], Some("spand001".to_string()));
+        assert_eq!(dictionary_values(&batch, "event_category"), ["single", "stolen_base", "single", "generic_out", "home_run"].map(|category| Some(category.to_string())));
+
+        // the two plays by hudso001 share a dictionary entry
+        let batters = batch.column_by_name("batter_id").unwrap().as_any().downcast_ref::<DictionaryArray<Int32Type>>().unwrap();
+        assert_eq!(batters.values().len(), 4);
+
+        let balls = batch.column_by_name("balls").unwrap().as_primitive::<arrow::datatypes::UInt8Type>();
+        assert!(balls.is_null(4));
+        assert_eq!(batch.column_by_name("away_score").unwrap().as_primitive::<arrow::datatypes::UInt8Type>().value(4), 1);
+    }
+
+    #[test]
+    fn games_batch() {
+        let batch = ColumnarExporter::new().games_batch(&games()).unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(dictionary_values(&batch, "home_team_id"), vec![Some("ANA".to_string())]);
+        assert_eq!(batch.column_by_name("attendance").unwrap().as_primitive::<arrow::datatypes::UInt32Type>().value(0), 43504);
+        assert_eq!(batch.column_by_name("home_score").unwrap().as_primitive::<arrow::datatypes::UInt8Type>().value(0), 1);
+    }
+
+    #[test]
+    fn writes_parquet() {
+        let path = std::env::temp_dir().join(format!("rstrosheet-plays-{}.parquet", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        let games = [games(), games()].concat();
+        ColumnarExporter::with_games_per_batch(1).write_plays_parquet(&games, file).unwrap();
+
+        // each game is its own batch, all through the same writer
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 10);
+        assert_eq!(reader.metadata().file_metadata().schema_descr().num_columns(), plays_schema().fields().len());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,443 @@
+use std::io::Write;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+use crate::chadwick::EventCategory;
+use crate::earned_runs::{self, RunEarned};
+use crate::export::{csv_writer, flag};
+use crate::parser::game::{Base, BattedBall, Count, Event, EventModifier, Game, Play, Position, RunnerAdvance, Team, Trajectory};
+
+/// A column of the per-play export, named as in Chadwick's `cwevent`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, EnumString)]
+pub enum EventField {
+    #[strum(serialize = "GAME_ID")]
+    GameId,
+    #[strum(serialize = "AWAY_TEAM_ID")]
+    AwayTeamId,
+    #[strum(serialize = "INN_CT")]
+    Inning,
+    #[strum(serialize = "BAT_HOME_ID")]
+    BattingTeam,
+    #[strum(serialize = "OUTS_CT")]
+    Outs,
+    #[strum(serialize = "BALLS_CT")]
+    Balls,
+    #[strum(serialize = "STRIKES_CT")]
+    Strikes,
+    #[strum(serialize = "PITCH_SEQ_TX")]
+    PitchSequence,
+    #[strum(serialize = "AWAY_SCORE_CT")]
+    AwayScore,
+    #[strum(serialize = "HOME_SCORE_CT")]
+    HomeScore,
+    #[strum(serialize = "BAT_ID")]
+    BatterId,
+    #[strum(serialize = "PIT_ID")]
+    PitcherId,
+    #[strum(serialize = "POS2_FLD_ID")]
+    CatcherId,
+    #[strum(serialize = "POS3_FLD_ID")]
+    FirstBaseId,
+    #[strum(serialize = "POS4_FLD_ID")]
+    SecondBaseId,
+    #[strum(serialize = "POS5_FLD_ID")]
+    ThirdBaseId,
+    #[strum(serialize = "POS6_FLD_ID")]
+    ShortstopId,
+    #[strum(serialize = "POS7_FLD_ID")]
+    LeftFieldId,
+    #[strum(serialize = "POS8_FLD_ID")]
+    CenterFieldId,
+    #[strum(serialize = "POS9_FLD_ID")]
+    RightFieldId,
+    #[strum(serialize = "BASE1_RUN_ID")]
+    FirstBaseRunnerId,
+    #[strum(serialize = "BASE2_RUN_ID")]
+    SecondBaseRunnerId,
+    #[strum(serialize = "BASE3_RUN_ID")]
+    ThirdBaseRunnerId,
+    #[strum(serialize = "EVENT_TX")]
+    EventText,
+    #[strum(serialize = "EVENT_CD")]
+    EventCode,
+    #[strum(serialize = "BAT_EVENT_FL")]
+    BatterEvent,
+    #[strum(serialize = "AB_FL")]
+    AtBat,
+    #[strum(serialize = "H_CD")]
+    Hit,
+    #[strum(serialize = "SH_FL")]
+    SacrificeHit,
+    #[strum(serialize = "SF_FL")]
+    SacrificeFly,
+    #[strum(serialize = "EVENT_OUTS_CT")]
+    EventOuts,
+    #[strum(serialize = "DP_FL")]
+    DoublePlay,
+    #[strum(serialize = "TP_FL")]
+    TriplePlay,
+    #[strum(serialize = "RBI_CT")]
+    Rbi,
+    #[strum(serialize = "BATTEDBALL_CD")]
+    BattedBallType,
+    #[strum(serialize = "BATTEDBALL_LOC_TX")]
+    HitLocation,
+    #[strum(serialize = "BAT_DEST_ID")]
+    BatterDestination,
+    #[strum(serialize = "RUN1_DEST_ID")]
+    FirstBaseRunnerDestination,
+    #[strum(serialize = "RUN2_DEST_ID")]
+    SecondBaseRunnerDestination,
+    #[strum(serialize = "RUN3_DEST_ID")]
+    ThirdBaseRunnerDestination,
+}
+
+impl EventField {
+    pub const ALL: [EventField; 40] = [
+        EventField::GameId,
+        EventField::AwayTeamId,
+        EventField::Inning,
+        EventField::BattingTeam,
+        EventField::Outs,
+        EventField::Balls,
+        EventField::Strikes,
+        EventField::PitchSequence,
+        EventField::AwayScore,
+        EventField::HomeScore,
+        EventField::BatterId,
+        EventField::PitcherId,
+        EventField::CatcherId,
+        EventField::FirstBaseId,
+        EventField::SecondBaseId,
+        EventField::ThirdBaseId,
+        EventField::ShortstopId,
+        EventField::LeftFieldId,
+        EventField::CenterFieldId,
+        EventField::RightFieldId,
+        EventField::FirstBaseRunnerId,
+        EventField::SecondBaseRunnerId,
+        EventField::ThirdBaseRunnerId,
+        EventField::EventText,
+        EventField::EventCode,
+        EventField::BatterEvent,
+        EventField::AtBat,
+        EventField::Hit,
+        EventField::SacrificeHit,
+        EventField::SacrificeFly,
+        EventField::EventOuts,
+        EventField::DoublePlay,
+        EventField::TriplePlay,
+        EventField::Rbi,
+        EventField::BattedBallType,
+        EventField::HitLocation,
+        EventField::BatterDestination,
+        EventField::FirstBaseRunnerDestination,
+        EventField::SecondBaseRunnerDestination,
+        EventField::ThirdBaseRunnerDestination,
+    ];
+}
+
+/// The state of the game before the play that a row needs beyond the play itself.
+struct PlayContext<'a> {
+    game: &'a Game,
+    play: &'a Play,
+    away_score: u8,
+    home_score: u8,
+    /// The runs scored on the play, as `earned_runs::classify_runs` decided them.
+    runs: &'a [(RunnerAdvance, Team, RunEarned)],
+}
+
+/// Writes one CSV row per play in the style of `cwevent`, with a header row of the
+/// selected field names.
+pub struct EventExporter {
+    fields: Vec<EventField>,
+}
+
+impl Default for EventExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventExporter {
+    pub fn new() -> Self {
+        Self::with_fields(EventField::ALL.to_vec())
+    }
+
+    pub fn with_fields(fields: Vec<EventField>) -> Self {
+        Self { fields }
+    }
+
+    /// Selects fields by their `cwevent` names, such as `GAME_ID,EVENT_CD`.
+    pub fn from_field_names(names: &str) -> Result<Self, String> {
+        let fields = names
+            .split(',')
+            .map(|name| EventField::from_str(name.trim()).map_err(|_| format!("Unknown event field: {name}")))
+            .collect::<Result<Vec<EventField>, String>>()?;
+
+        Ok(Self::with_fields(fields))
+    }
+
+    pub fn get_fields(&self) -> &Vec<EventField> {
+        &self.fields
+    }
+
+    pub fn rows(&self, game: &Game) -> Vec<Vec<String>> {
+        let mut rows = Vec::new();
+        let (mut away_score, mut home_score) = (0, 0);
+        let classified_runs = earned_runs::classify_runs(game);
+        let mut remaining_runs = classified_runs.as_slice();
+
+        for play in game.get_plays() {
+            let (runs, rest) = remaining_runs.split_at((play.get_runs() as usize).min(remaining_runs.len()));
+            remaining_runs = rest;
+
+            let context = PlayContext { game, play, away_score, home_score, runs };
+            rows.push(self.fields.iter().map(|field| context.value(field)).collect());
+
+            match play.get_team() {
+                Team::Visiting => away_score += play.get_runs(),
+                Team::Home => home_score += play.get_runs(),
+            }
+        }
+
+        rows
+    }
+
+    pub fn write<W: Write>(&self, games: &[Game], writer: W) -> Result<(), String> {
+        let mut writer = csv_writer(writer);
+
+        writer
+            .write_record(self.fields.iter().map(EventField::to_string))
+            .map_err(|e| e.to_string())?;
+
+        for game in games {
+            for row in self.rows(game) {
+                writer.write_record(&row).map_err(|e| e.to_string())?;
+            }
+        }
+
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+impl PlayContext<'_> {
+    fn value(&self, field: &EventField) -> String {
+        let play = self.play;
+        let event = play.get_event();
+
+        match field {
+            EventField::GameId => self.game.get_id().to_string(),
+            EventField::AwayTeamId => play.get_info_state().visiting_team.clone().unwrap_or_default(),
+            EventField::Inning => play.get_inning().to_string(),
+            EventField::BattingTeam => match play.get_team() {
+                Team::Visiting => "0".to_string(),
+                Team::Home => "1".to_string(),
+            },
+            EventField::Outs => play.get_outs().to_string(),
+            EventField::Balls => match play.get_count() {
+                Some(Count::Known { balls, .. }) => balls.to_string(),
+                _ => String::new(),
+            },
+            EventField::Strikes => match play.get_count() {
+                Some(Count::Known { strikes, .. }) => strikes.to_string(),
+                _ => String::new(),
+            },
+            EventField::PitchSequence => play
+                .get_pitches()
+                .map(|pitches| pitches.iter().map(|pitch| pitch.to_string()).collect())
+                .unwrap_or_default(),
+            EventField::AwayScore => self.away_score.to_string(),
+            EventField::HomeScore => self.home_score.to_string(),
+            EventField::BatterId => play.get_batter_id().to_string(),
+            EventField::PitcherId => self.fielder(Position::Pitcher),
+            EventField::CatcherId => self.fielder(Position::Catcher),
+            EventField::FirstBaseId => self.fielder(Position::FirstBase),
+            EventField::SecondBaseId => self.fielder(Position::SecondBase),
+            EventField::ThirdBaseId => self.fielder(Position::ThirdBase),
+            EventField::ShortstopId => self.fielder(Position::Shortstop),
+            EventField::LeftFieldId => self.fielder(Position::LeftField),
+            EventField::CenterFieldId => self.fielder(Position::CenterField),
+            EventField::RightFieldId => self.fielder(Position::RightField),
+            EventField::FirstBaseRunnerId => self.runner(Base::First),
+            EventField::SecondBaseRunnerId => self.runner(Base::Second),
+            EventField::ThirdBaseRunnerId => self.runner(Base::Third),
+            EventField::EventText => play.get_event_text().to_string(),
+            EventField::EventCode => event
+                .map(|event| EventCategory::from_event(event).code())
+                .unwrap_or(EventCategory::Unknown.code())
+                .to_string(),
+            EventField::BatterEvent => flag(event.is_some_and(|event| event.event_type.ends_plate_appearance())),
+            EventField::AtBat => flag(event.is_some_and(|event| event.is_at_bat())),
+            EventField::Hit => event.map_or(0, |event| event.event_type.total_bases()).to_string(),
+            EventField::SacrificeHit => flag(self.has_modifier(&EventModifier::SacrificeHitOrBunt)),
+            EventField::SacrificeFly => flag(self.has_modifier(&EventModifier::SacrificeFly)),
+            EventField::EventOuts => event.map_or(0, |event| event.outs_recorded()).to_string(),
+            EventField::DoublePlay => flag(event.is_some_and(Event::is_double_play)),
+            EventField::TriplePlay => flag(event.is_some_and(Event::is_triple_play)),
+            EventField::Rbi => play.get_rbi().to_string(),
+            EventField::BattedBallType => event
+                .and_then(|event| event.modifiers.iter().find_map(batted_ball_type))
+                .unwrap_or_default()
+                .to_string(),
+            EventField::HitLocation => event
+                .and_then(Event::get_batted_ball)
+                .and_then(|batted_ball| batted_ball.location.as_ref())
+                .map(|location| location.to_string())
+                .unwrap_or_default(),
+            EventField::BatterDestination => self.destination(Base::Home).to_string(),
+            EventField::FirstBaseRunnerDestination => self.destination(Base::First).to_string(),
+            EventField::SecondBaseRunnerDestination => self.destination(Base::Second).to_string(),
+            EventField::ThirdBaseRunnerDestination => self.destination(Base::Third).to_string(),
+        }
+    }
+
+    fn fielder(&self, position: Position) -> String {
+        self.play.get_defense().get(&position).cloned().unwrap_or_default()
+    }
+
+    fn runner(&self, base: Base) -> String {
+        self.play.get_runner(&base).map(|runner| runner.id.clone()).unwrap_or_default()
+    }
+
+    fn has_modifier(&self, modifier: &EventModifier) -> bool {
+        self.play.get_event().is_some_and(|event| event.modifiers.contains(modifier))
+    }
+
+    /// Where the runner starting on the base ended up, as `cwevent` codes it: 0 for out or
+    /// absent, the base they finished on, or 4, 5 and 6 for an earned, unearned and team
+    /// unearned run, as `classify_runs` decides them.
+    fn destination(&self, base: Base) -> u8 {
+        let runner_advance = self
+            .play
+            .get_runner_advances()
+            .iter()
+            .find(|runner_advance| runner_advance.advance.starting_base == base);
+
+        let Some(runner_advance) = runner_advance else {
+            return match base.index() {
+                Some(index) if self.play.get_runner(&base).is_some() => index as u8 + 1,
+                _ => 0,
+            };
+        };
+
+        let advance = &runner_advance.advance;
+        if advance.out {
+            0
+        } else if advance.is_run() {
+            let run_earned = self.runs
+                .iter()
+                .find(|(run, _, _)| run == runner_advance)
+                .map(|(_, _, run_earned)| *run_earned);
+
+            match run_earned {
+                Some(RunEarned { pitcher: false, .. }) => 5,
+                Some(RunEarned { team: false, .. }) => 6,
+                _ => 4,
+            }
+        } else {
+            advance.ending_base.index().map_or(0, |index| index as u8 + 1)
+        }
+    }
+}
+
+/// `cwevent`'s batted ball type, where bunts share the code of their trajectory.
+fn batted_ball_type(modifier: &EventModifier) -> Option<&'static str> {
+    match modifier {
+        EventModifier::BattedBall(BattedBall { trajectory: Some(trajectory), .. }) => match trajectory {
+            Trajectory::GroundBall | Trajectory::GroundBallBunt => Some("G"),
+            Trajectory::LineDrive | Trajectory::LineDriveBunt => Some("L"),
+            Trajectory::Fly => Some("F"),
+            Trajectory::PopFly | Trajectory::PopUpBunt => Some("P"),
+        },
+        EventModifier::GroundBallDoublePlay
+        | EventModifier::GroundBallTriplePlay
+        | EventModifier::BuntGroundedIntoDoublePlay => Some("G"),
+        EventModifier::LinedIntoDoublePlay | EventModifier::LinedIntoTriplePlay => Some("L"),
+        EventModifier::FlyBallDoublePlay => Some("F"),
+        EventModifier::BuntPoppedIntoDoublePlay => Some("P"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::parser::FileParser;
+
+    fn rows(exporter: &EventExporter) -> Vec<Vec<String>> {
+        let games = fixtures::parse(fixtures::CWEVENT_FILE);
+        exporter.rows(&games[0])
+    }
+
+    #[test]
+    fn export_selected_fields() {
+        let exporter = EventExporter::from_field_names("GAME_ID,INN_CT,OUTS_CT,BASE1_RUN_ID,BASE2_RUN_ID,EVENT_TX,EVENT_CD,H_CD,RBI_CT,AWAY_SCORE_CT").unwrap();
+
+        assert_eq!(rows(&exporter), vec![
+            vec!["ANA201004050", "1", "0", "", "", "S7/L7", "20", "1", "0", "0"],
+            vec!["ANA201004050", "1", "0", "spand001", "", "SB2", "4", "0", "0", "0"],
+            vec!["ANA201004050", "1", "0", "", "spand001", "S9/L9D.2-H", "20", "1", "1", "0"],
+            vec!["ANA201004050", "1", "0", "hudso001", "", "64(1)3/GDP", "2", "0", "0", "1"],
+        ]);
+    }
+
+    #[test]
+    fn export_counts_and_batted_balls() {
+        let exporter = EventExporter::from_field_names("BALLS_CT,STRIKES_CT,PITCH_SEQ_TX,BATTEDBALL_CD,BATTEDBALL_LOC_TX,PIT_ID,POS2_FLD_ID").unwrap();
+
+        assert_eq!(rows(&exporter)[2], vec!["2", "2", "BCFB>X", "L", "9D", "weavj003", "napom001"]);
+    }
+
+    #[test]
+    fn export_runner_destinations() {
+        let exporter = EventExporter::from_field_names("BAT_DEST_ID,RUN1_DEST_ID,RUN2_DEST_ID,RUN3_DEST_ID,EVENT_OUTS_CT,DP_FL,AB_FL").unwrap();
+        let rows = rows(&exporter);
+
+        assert_eq!(rows[1], vec!["0", "2", "0", "0", "0", "F", "F"]);
+        assert_eq!(rows[2], vec!["1", "0", "4", "0", "0", "F", "T"]);
+        assert_eq!(rows[3], vec!["0", "0", "0", "0", "2", "T", "T"]);
+    }
+
+    #[test]
+    fn unearned_runs_without_markers() {
+        let file = "id,ANA201004050
+version,2
+info,visteam,MIN
+info,hometeam,ANA
+start,spand001,\"Denard Span\",0,1,8
+start,hudso001,\"Orlando Hudson\",0,2,4
+start,weavj003,\"Jered Weaver\",1,0,1
+play,1,0,spand001,00,X,E6
+play,1,0,hudso001,00,X,HR/F7.1-H";
+        let games = FileParser::new().parse_file(file).unwrap();
+        let exporter = EventExporter::from_field_names("BAT_DEST_ID,RUN1_DEST_ID").unwrap();
+
+        // span reached on an error, so his run is unearned
+        assert_eq!(exporter.rows(&games[0])[1], vec!["4", "5"]);
+    }
+
+    #[test]
+    fn write_header_and_rows() {
+        let exporter = EventExporter::with_fields(vec![EventField::GameId, EventField::EventText]);
+        let games = fixtures::parse(fixtures::CWEVENT_FILE);
+
+        let mut output = Vec::new();
+        exporter.write(&games, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().collect::<Vec<&str>>(), vec![
+            "GAME_ID,EVENT_TX",
+            "ANA201004050,S7/L7",
+            "ANA201004050,SB2",
+            "ANA201004050,S9/L9D.2-H",
+            "ANA201004050,64(1)3/GDP",
+        ]);
+    }
+
+    #[test]
+    fn reject_unknown_field_names() {
+        assert!(EventExporter::from_field_names("GAME_ID,NOT_A_FIELD").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use std::io::Write;
+
+use crate::chadwick;
+use crate::export::{csv_writer, flag};
+use crate::parser::game::{AttendanceInfo, Game, Position, Team, TemperatureInfo, TimeOfGameInfo, WindSpeedInfo};
+
+const FIELDS: [&str; 76] = [
+    "GAME_ID",
+    "GAME_DT",
+    "GAME_CT",
+    "START_GAME_TM",
+    "DAYNIGHT_PARK_CD",
+    "INN_SCHED_CT",
+    "TIEBREAK_BASE_CD",
+    "DH_FL",
+    "PITCHES_RECORD_CD",
+    "SCORER_RECORD_ID",
+    "HOME_BAT_FIRST_FL",
+    "AWAY_TEAM_ID",
+    "HOME_TEAM_ID",
+    "PARK_ID",
+    "BASE4_UMP_ID",
+    "BASE1_UMP_ID",
+    "BASE2_UMP_ID",
+    "BASE3_UMP_ID",
+    "LF_UMP_ID",
+    "RF_UMP_ID",
+    "FIELD_PARK_CD",
+    "PRECIP_PARK_CD",
+    "SKY_PARK_CD",
+    "TEMP_PARK_CT",
+    "WIND_DIRECTION_PARK_CD",
+    "WIND_SPEED_PARK_CT",
+    "MINUTES_GAME_CT",
+    "ATTEND_PARK_CT",
+    "WIN_PIT_ID",
+    "LOSE_PIT_ID",
+    "SAVE_PIT_ID",
+    "AWAY_START_PIT_ID",
+    "AWAY_LINEUP1_BAT_ID",
+    "AWAY_LINEUP1_FLD_CD",
+    "AWAY_LINEUP2_BAT_ID",
+    "AWAY_LINEUP2_FLD_CD",
+    "AWAY_LINEUP3_BAT_ID",
+    "AWAY_LINEUP3_FLD_CD",
+    "AWAY_LINEUP4_BAT_ID",
+    "AWAY_LINEUP4_FLD_CD",
+    "AWAY_LINEUP5_BAT_ID",
+    "AWAY_LINEUP5_FLD_CD",
+    "AWAY_LINEUP6_BAT_ID",
+    "AWAY_LINEUP6_FLD_CD",
+    "AWAY_LINEUP7_BAT_ID",
+    "AWAY_LINEUP7_FLD_CD",
+    "AWAY_LINEUP8_BAT_ID",
+    "AWAY_LINEUP8_FLD_CD",
+    "AWAY_LINEUP9_BAT_ID",
+    "AWAY_LINEUP9_FLD_CD",
+    "HOME_START_PIT_ID",
+    "HOME_LINEUP1_BAT_ID",
+    "HOME_LINEUP1_FLD_CD",
+    "HOME_LINEUP2_BAT_ID",
+    "HOME_LINEUP2_FLD_CD",
+    "HOME_LINEUP3_BAT_ID",
+    "HOME_LINEUP3_FLD_CD",
+    "HOME_LINEUP4_BAT_ID",
+    "HOME_LINEUP4_FLD_CD",
+    "HOME_LINEUP5_BAT_ID",
+    "HOME_LINEUP5_FLD_CD",
+    "HOME_LINEUP6_BAT_ID",
+    "HOME_LINEUP6_FLD_CD",
+    "HOME_LINEUP7_BAT_ID",
+    "HOME_LINEUP7_FLD_CD",
+    "HOME_LINEUP8_BAT_ID",
+    "HOME_LINEUP8_FLD_CD",
+    "HOME_LINEUP9_BAT_ID",
+    "HOME_LINEUP9_FLD_CD",
+    "GWRBI_BAT_ID",
+    "GAME_TYPE_CD",
+    "INN_CT",
+    "AWAY_SCORE_CT",
+    "HOME_SCORE_CT",
+    "AWAY_LINE_TX",
+    "HOME_LINE_TX",
+];
+
+/// Writes one CSV row per game in the style of `cwgame`: the info records with Chadwick's
+/// codes, the starting lineups, the final score and the line score.
+pub struct GameExporter;
+
+impl Default for GameExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameExporter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn headers(&self) -> Vec<String> {
+        FIELDS.iter().map(|field| field.to_string()).collect()
+    }
+
+    pub fn row(&self, game: &Game) -> Vec<String> {
+        let info = game.get_info_state();
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let display = |value: Option<String>| value.unwrap_or_default();
+
+        let mut row = vec![
+            game.get_id().to_string(),
+            text(&info.date),
+            display(info.number.map(|n| n.to_string())),
+            text(&info.start_time),
+            display(info.day_night.map(|d| chadwick::day_night_code(&d).to_string())),
+            display(info.innings.map(|n| n.to_string())),
+            display(info.tiebreaker.map(|n| n.to_string())),
+            display(info.used_designated_hitter_rule.map(flag)),
+            display(info.pitches.map(|p| chadwick::pitches_code(&p).to_string())),
+            text(&info.official_scorer),
+            display(info.home_team_bat_first.map(flag)),
+            text(&info.visiting_team),
+            text(&info.home_team),
+            text(&info.site),
+            text(&info.umpire_home),
+            text(&info.umpire_1b),
+            text(&info.umpire_2b),
+            text(&info.umpire_3b),
+            text(&info.umpire_left_field),
+            text(&info.umpire_right_field),
+            display(info.field_condition.map(|f| chadwick::field_condition_code(&f).to_string())),
+            display(info.precipitation.map(|p| chadwick::precipitation_code(&p).to_string())),
+            display(info.sky.map(|s| chadwick::sky_code(&s).to_string())),
+            display(info.temperature.and_then(|t| match t {
+                TemperatureInfo::Known(degrees) => Some(degrees.to_string()),
+                TemperatureInfo::Unknown => None,
+            })),
+            display(info.wind_direction.map(|w| chadwick::wind_direction_code(&w).to_string())),
+            display(info.wind_speed.and_then(|w| match w {
+                WindSpeedInfo::Known(speed) => Some(speed.to_string()),
+                WindSpeedInfo::Unknown => None,
+            })),
+            display(info.time_of_game.and_then(|t| match t {
+                TimeOfGameInfo::Known(minutes) => Some(minutes.to_string()),
+                TimeOfGameInfo::Unknown => None,
+            })),
+            display(info.attendance.and_then(|a| match a {
+                AttendanceInfo::Known(attendance) => Some(attendance.to_string()),
+                AttendanceInfo::Unknown => None,
+            })),
+            text(&info.wp),
+            text(&info.lp),
+            text(&info.save),
+        ];
+
+        for team in [Team::Visiting, Team::Home] {
+            let starters = game.get_starters(&team);
+
+            let starting_pitcher = starters.iter().find(|entry| entry.position == Position::Pitcher);
+            row.push(starting_pitcher.map(|entry| entry.player_id.clone()).unwrap_or_default());
+
+            for slot in 1..=9 {
+                let starter = starters.iter().find(|entry| entry.batting_order == slot);
+                row.push(starter.map(|entry| entry.player_id.clone()).unwrap_or_default());
+                row.push(starter.map(|entry| entry.position.to_string()).unwrap_or_default());
+            }
+        }
+
+        let line_score = game.get_line_score();
+        row.extend([
+            text(&info.game_winning_rbi),
+            display(info.game_type.map(|g| chadwick::game_type_code(&g).to_string())),
+            line_score.visiting.len().max(line_score.home.len()).to_string(),
+            line_score.get_runs(&Team::Visiting).to_string(),
+            line_score.get_runs(&Team::Home).to_string(),
+            line_text(&line_score.visiting, line_score.visiting.len()),
+            line_text(&line_score.home, line_score.visiting.len()),
+        ]);
+
+        row
+    }
+
+    pub fn write<W: Write>(&self, games: &[Game], writer: W) -> Result<(), String> {
+        let mut writer = csv_writer(writer);
+
+        writer.write_record(self.headers()).map_err(|e| e.to_string())?;
+        for game in games {
+            writer.write_record(self.row(game)).map_err(|e| e.to_string())?;
+        }
+
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+/// Runs by inning as `cwgame` writes them, with double figures in parentheses and an `x`
+/// for each inning the team didn't need to bat.
+fn line_text(runs: &[u8], innings: usize) -> String {
+    let mut text = runs
+        .iter()
+        .map(|runs| if *runs < 10 { runs.to_string() } else { format!("({runs})") })
+        .collect::<String>();
+
+    for _ in runs.len()..innings {
+        text.push('x');
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn row() -> Vec<(String, String)> {
+        let exporter = GameExporter::new();
+        let games = fixtures::parse(fixtures::CWGAME_FILE);
+
+        exporter.headers().into_iter().zip(exporter.row(&games[0])).collect()
+    }
+
+    fn value(row: &[(String, String)], header: &str) -> String {
+        row.iter().find(|(h, _)| h == header).unwrap().1.clone()
+    }
+
+    #[test]
+    fn headers_match_row() {
+        let games = fixtures::parse(fixtures::CWGAME_FILE);
+        let exporter = GameExporter::new();
+
+        assert_eq!(exporter.headers().len(), exporter.row(&games[0]).len());
+    }
+
+    #[test]
+    fn export_info_records() {
+        let row = row();
+
+        assert_eq!(value(&row, "GAME_DT"), "2010/04/05");
+        assert_eq!(value(&row, "DAYNIGHT_PARK_CD"), "N");
+        assert_eq!(value(&row, "DH_FL"), "T");
+        assert_eq!(value(&row, "PARK_ID"), "ANA01");
+        assert_eq!(value(&row, "BASE4_UMP_ID"), "drakr901");
+        assert_eq!(value(&row, "BASE1_UMP_ID"), "");
+        assert_eq!(value(&row, "TEMP_PARK_CT"), "64");
+        assert_eq!(value(&row, "MINUTES_GAME_CT"), "164");
+        assert_eq!(value(&row, "ATTEND_PARK_CT"), "43504");
+        assert_eq!(value(&row, "WIN_PIT_ID"), "weavj003");
+        assert_eq!(value(&row, "SAVE_PIT_ID"), "");
+        assert_eq!(value(&row, "GAME_TYPE_CD"), "R");
+    }
+
+    #[test]
+    fn export_starting_lineups() {
+        let row = row();
+
+        assert_eq!(value(&row, "AWAY_START_PIT_ID"), "bakes002");
+        assert_eq!(value(&row, "AWAY_LINEUP1_BAT_ID"), "spand001");
+        assert_eq!(value(&row, "AWAY_LINEUP1_FLD_CD"), "8");
+        assert_eq!(value(&row, "HOME_LINEUP2_BAT_ID"), "abreb001");
+        assert_eq!(value(&row, "HOME_LINEUP2_FLD_CD"), "9");
+        assert_eq!(value(&row, "HOME_LINEUP3_BAT_ID"), "");
+    }
+
+    #[test]
+    fn export_score_and_line_score() {
+        let row = row();
+
+        assert_eq!(value(&row, "INN_CT"), "2");
+        assert_eq!(value(&row, "AWAY_SCORE_CT"), "0");
+        assert_eq!(value(&row, "HOME_SCORE_CT"), "1");
+        assert_eq!(value(&row, "AWAY_LINE_TX"), "00");
+        assert_eq!(value(&row, "HOME_LINE_TX"), "1x");
+    }
+
+    #[test]
+    fn write_row_with_chadwick_codes() {
+        let games = fixtures::parse(fixtures::CWGAME_FILE);
+        let mut output = Vec::new();
+        GameExporter::new().write(&games, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let row = output.lines().nth(1).unwrap();
+
+        assert_eq!(
+            row,
+            "ANA201004050,2010/04/05,0,,N,9,0,T,2,,F,MIN,ANA,ANA01,drakr901,,,,,,4,1,4,64,1,7,164,43504,weavj003,bakes002,,\
+             bakes002,spand001,8,,,,,,,,,,,,,,,,,\
+             weavj003,,,abreb001,9,,,,,,,,,,,,,,,\
+             ,R,2,0,1,00,1x"
+        );
+    }
+
+    #[test]
+    fn line_text_with_double_figures() {
+        assert_eq!(line_text(&[0, 12, 3], 3), "0(12)3");
+        assert_eq!(line_text(&[1, 0], 3), "10x");
+    }
+}
@@ -0,0 +1,171 @@
+use std::io::Write;
+
+use serde_json::{Value, json};
+
+use crate::parser::game::{Base, Game, Play, Team};
+use crate::parser::roster::Roster;
+
+/// Writes one JSON object per play, one per line, with the state of the game before the play
+/// and the play itself. Hands are only filled in for players found in the roster.
+pub struct PlayJsonExporter {
+    roster: Roster,
+}
+
+impl Default for PlayJsonExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlayJsonExporter {
+    pub fn new() -> Self {
+        Self::with_roster(Roster::new())
+    }
+
+    pub fn with_roster(roster: Roster) -> Self {
+        Self { roster }
+    }
+
+    pub fn records(&self, game: &Game) -> Vec<Value> {
+        let mut records = Vec::new();
+        let (mut away_score, mut home_score) = (0, 0);
+
+        for play in game.get_plays() {
+            records.push(self.record(game, play, away_score, home_score));
+
+            match play.get_team() {
+                Team::Visiting => away_score += play.get_runs(),
+                Team::Home => home_score += play.get_runs(),
+            }
+        }
+
+        records
+    }
+
+    /// Writes every game's plays, flushing after each game so a season can be streamed.
+    pub fn write<W: Write>(&self, games: &[Game], mut writer: W) -> Result<(), String> {
+        for game in games {
+            for record in self.records(game) {
+                serde_json::to_writer(&mut writer, &record).map_err(|e| e.to_string())?;
+                writer.write_all(b"\n").map_err(|e| e.to_string())?;
+            }
+            writer.flush().map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn record(&self, game: &Game, play: &Play, away_score: u8, home_score: u8) -> Value {
+        let runner = |base: Base| play.get_runner(&base).map(|runner| runner.id.clone());
+        let batter_id = play.get_batter_id();
+        let pitcher_id = play.get_pitcher_id();
+
+        let advances = play
+            .get_runner_advances()
+            .iter()
+            .map(|runner_advance| {
+                let advance = &runner_advance.advance;
+                json!({
+                    "runner_id": runner_advance.runner.id,
+                    "from": if advance.starting_base == Base::Home { "B".to_string() } else { advance.starting_base.to_string() },
+                    "to": advance.ending_base,
+                    "out": advance.is_out(),
+                    "run": advance.is_run(),
+                    "rbi": play.credits_rbi(advance),
+                    "responsible_pitcher_id": runner_advance.runner.pitcher_id,
+                })
+            })
+            .collect::<Vec<Value>>();
+
+        json!({
+            "game_id": game.get_id(),
+            "inning": play.get_inning(),
+            "half": match play.get_team() {
+                Team::Visiting => "top",
+                Team::Home => "bottom",
+            },
+            "outs": play.get_outs(),
+            "runners": {
+                "first": runner(Base::First),
+                "second": runner(Base::Second),
+                "third": runner(Base::Third),
+            },
+            "score": {
+                "visiting": away_score,
+                "home": home_score,
+            },
+            "batter": {
+                "id": batter_id,
+                "hand": self.roster.get_batting_hand(batter_id, pitcher_id.map(String::as_str)),
+            },
+            "pitcher": {
+                "id": pitcher_id,
+                "hand": pitcher_id.and_then(|pitcher_id| self.roster.get_throwing_hand(pitcher_id)),
+            },
+            "count": play.get_count(),
+            "pitches": play.get_pitches().map(|pitches| pitches.iter().map(|pitch| pitch.to_string()).collect::<String>()),
+            "event_text": play.get_event_text(),
+            "event": play.get_event(),
+            "advances": advances,
+            "outs_on_play": play.get_runner_advances().iter().filter(|runner_advance| runner_advance.advance.out).count(),
+            "runs_on_play": play.get_runs(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    const ROSTER: &str = "spand001,Span,Denard,L,L,MIN,OF
+hudso001,Hudson,Orlando,B,R,MIN,2B
+weavj003,Weaver,Jered,R,R,ANA,P
+";
+
+    fn records() -> Vec<Value> {
+        let mut roster = Roster::new();
+        roster.parse_file(ROSTER).unwrap();
+
+        let games = fixtures::parse(fixtures::JSONL_FILE);
+        PlayJsonExporter::with_roster(roster).records(&games[0])
+    }
+
+    #[test]
+    fn play_context() {
+        let records = records();
+        let single = &records[2];
+
+        assert_eq!(single["game_id"], "ANA201004050");
+        assert_eq!(single["half"], "top");
+        assert_eq!(single["runners"], json!({ "first": null, "second": "spand001", "third": null }));
+        assert_eq!(single["batter"], json!({ "id": "hudso001", "hand": "L" }));
+        assert_eq!(single["pitcher"], json!({ "id": "weavj003", "hand": "R" }));
+        assert_eq!(single["count"], "22");
+        assert_eq!(single["pitches"], "B.CFB>X");
+        assert_eq!(single["event"]["modifiers"], json!(["L9D"]));
+        assert_eq!(single["runs_on_play"], 1);
+
+        let runs = single["advances"].as_array().unwrap();
+        assert!(runs.contains(&json!({ "runner_id": "spand001", "from": "2", "to": "H", "out": false, "run": true, "rbi": true, "responsible_pitcher_id": "weavj003" })));
+        assert!(runs.contains(&json!({ "runner_id": "hudso001", "from": "B", "to": "1", "out": false, "run": false, "rbi": false, "responsible_pitcher_id": "weavj003" })));
+
+        assert_eq!(records[3]["outs_on_play"], 2);
+        assert_eq!(records[4]["score"], json!({ "visiting": 1, "home": 0 }));
+        assert_eq!(records[4]["batter"]["hand"], Value::Null);
+        assert_eq!(records[4]["pitcher"]["hand"], Value::Null);
+    }
+
+    #[test]
+    fn writes_one_line_per_play() {
+        let games = fixtures::parse(fixtures::JSONL_FILE);
+        let mut output = Vec::new();
+        PlayJsonExporter::new().write(&games, &mut output).unwrap();
+
+        let lines = String::from_utf8(output).unwrap().lines().map(String::from).collect::<Vec<String>>();
+        assert_eq!(lines.len(), 5);
+        for line in lines {
+            assert!(serde_json::from_str::<Value>(&line).unwrap().is_object());
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Event files shared by the tests, kept in `tests/data`.
+
+use crate::parser::FileParser;
+use crate::parser::game::{Game, Team};
+
+/// A full game written for the tests after the 2010 opener, with its box score in
+/// `BOX_SCORE_FILE`. It isn't Retrosheet's file for that game.
+pub(crate) const EVENT_FILE: &str = include_str!("../tests/data/2010ANA.EVA");
+
+/// Box score lines in Retrosheet's `stat` record layout. They were tallied by hand from the
+/// plays rather than taken from a published box score, so the box score proofs back them up.
+pub(crate) const BOX_SCORE_FILE: &str = include_str!("../tests/data/2010ANA.EBA");
+
+/// Two games with a sacrifice fly, a sacrifice bunt, an intentional walk and a hit batter.
+pub(crate) const BATTING_FILE: &str = include_str!("../tests/data/batting.EVA");
+
+/// A game with a pitching change mid-inning, a wild pitch, a balk and `data,er` records.
+pub(crate) const PITCHING_FILE: &str = include_str!("../tests/data/pitching.EVA");
+
+/// A game with a defensive switch, a passed ball and a caught stealing.
+pub(crate) const FIELDING_FILE: &str = include_str!("../tests/data/fielding.EVA");
+
+/// Plays with each trajectory and a range of locations and depths.
+pub(crate) const BATTED_BALL_FILE: &str = include_str!("../tests/data/batted_ball.EVA");
+
+/// An inning and a half with counts on most plays, shared by run expectancy and run values.
+pub(crate) const RUN_EXPECTANCY_FILE: &str = include_str!("../tests/data/run_expectancy.EVA");
+
+/// Three short games: a road win, a home win and one left tied.
+pub(crate) const WIN_EXPECTANCY_FILE: &str = include_str!("../tests/data/win_expectancy.EVA");
+
+/// Three short games, with a relief pitcher in the first.
+pub(crate) const LEVERAGE_FILE: &str = include_str!("../tests/data/leverage.EVA");
+
+/// A game where the reliever comes in two runs down, a state the leverage games never reach.
+pub(crate) const RELIEF_FILE: &str = include_str!("../tests/data/relief.EVA");
+
+/// An inning of the 2010 opener with the game's umpire, time and attendance.
+pub(crate) const OPENING_INNING_FILE: &str = include_str!("../tests/data/opening_inning.EVA");
+
+/// A game with every `info` record, comments and advances that need parentheses.
+pub(crate) const WRITER_FILE: &str = include_str!("../tests/data/writer.EVA");
+
+/// A few plays with comments, a substitution and a `data` record.
+#[cfg(feature = "sqlite")]
+pub(crate) const SQLITE_FILE: &str = include_str!("../tests/data/sqlite.EVA");
+
+/// The first few plays of the 2010 opener.
+#[cfg(feature = "serde")]
+pub(crate) const JSONL_FILE: &str = include_str!("../tests/data/jsonl.EVA");
+
+/// Like `JSONL_FILE`, with a date, attendance and an unknown count.
+#[cfg(feature = "arrow")]
+pub(crate) const COLUMNAR_FILE: &str = include_str!("../tests/data/columnar.EVA");
+
+/// The top of the first of the 2010 opener.
+pub(crate) const CWEVENT_FILE: &str = include_str!("../tests/data/cwevent.EVA");
+
+/// A short game with the `info` records cwgame reports.
+pub(crate) const CWGAME_FILE: &str = include_str!("../tests/data/cwgame.EVA");
+
+pub(crate) fn parse(file: &str) -> Vec<Game> {
+    FileParser::new().parse_file(file).unwrap()
+}
+
+pub(crate) fn games() -> Vec<Game> {
+    parse(EVENT_FILE)
+}
+
+/// Every `stat` line of a kind, such as `bline`, as its player id and the numbers after it.
+pub(crate) fn box_lines(kind: &str) -> Vec<(String, Vec<u32>)> {
+    BOX_SCORE_FILE
+        .lines()
+        .filter_map(|line| line.strip_prefix(&format!("stat,{kind},")))
+        .map(|line| {
+            let mut fields = line.split(',');
+            let player_id = fields.next().unwrap().to_string();
+            (player_id, fields.map(|field| field.parse().unwrap()).collect())
+        })
+        .collect()
+}
+
+/// Whether the player is in the team's lineup at any point in the game.
+pub(crate) fn plays_for(game: &Game, team: &Team, player_id: &str) -> bool {
+    game.get_lineup_entries()
+        .iter()
+        .any(|entry| entry.team == *team && entry.player_id == player_id)
+}
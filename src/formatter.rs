@@ -0,0 +1,115 @@
+use crate::parser::Line;
+
+/// A play record that isn't written the way the formatter would write it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonCanonicalLine {
+    /// Counting from 1.
+    pub line_number: usize,
+    pub line: String,
+    pub canonical: String,
+}
+
+/// Rewrites every play in an event file in one spelling, such as `HR` rather than `H`, `NR`
+/// rather than `NORBI` and modifiers in a fixed order. Every other record is left as it is.
+pub struct EventFileFormatter;
+
+impl Default for EventFileFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventFileFormatter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn format_line(&self, line: &str) -> Result<String, String> {
+        let line = line.trim_end();
+        if !line.starts_with("play,") {
+            return Ok(line.to_string());
+        }
+
+        match Line::parse(line)? {
+            Line::Play { inning, team, batter_id, count, pitches, event, note, .. } => {
+                let pitches = pitches.unwrap_or_default().iter().map(|pitch| pitch.to_string()).collect::<String>();
+                let note = note.map(|note| note.to_string()).unwrap_or_default();
+                Ok(format!("play,{inning},{team},{batter_id},{count},{pitches},{}{note}", event.to_canonical()))
+            },
+            _ => Ok(line.to_string()),
+        }
+    }
+
+    pub fn format(&self, file_content: &str) -> Result<String, String> {
+        let mut formatted = String::new();
+        for (index, line) in file_content.lines().enumerate() {
+            let line = self.format_line(line).map_err(|e| format!("line {}: {e}", index + 1))?;
+            formatted.push_str(&line);
+            formatted.push('\n');
+        }
+
+        Ok(formatted)
+    }
+
+    /// The lines the formatter would change, without changing them.
+    pub fn check(&self, file_content: &str) -> Result<Vec<NonCanonicalLine>, String> {
+        let mut non_canonical = Vec::new();
+        for (index, line) in file_content.lines().enumerate() {
+            let canonical = self.format_line(line).map_err(|e| format!("line {}: {e}", index + 1))?;
+            if canonical != line {
+                non_canonical.push(NonCanonicalLine {
+                    line_number: index + 1,
+                    line: line.to_string(),
+                    canonical,
+                });
+            }
+        }
+
+        Ok(non_canonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(event: &str) -> String {
+        let line = EventFileFormatter::new().format_line(&format!("play,1,0,spand001,??,,{event}")).unwrap();
+        line.trim_start_matches("play,1,0,spand001,??,,").to_string()
+    }
+
+    #[test]
+    fn canonical_spellings() {
+        assert_eq!(format("H/F9"), "HR/F9");
+        assert_eq!(format("HR9/F9.2-H(NORBI)"), "HR9/F9.2-H(NR)");
+        assert_eq!(format("S8/G/4.1-2"), "S8/G4.1-2");
+        assert_eq!(format("64(1)3/G6/GDP"), "64(1)3/GDP/G6");
+        assert_eq!(format("E6/G6/TH.B-2"), "E6/TH/G6.B-2");
+        assert_eq!(format("D7/L7.B-3;1-H;2-H"), "D7/L7.2-H;1-H;B-3");
+        assert_eq!(format("WP.3-H(NR)(UR)"), "WP.3-H(UR)(NR)");
+        assert_eq!(format("S9/L9S.3-H"), "S9/L9S.3-H");
+    }
+
+    #[test]
+    fn formats_only_play_records() {
+        let file = "id,ANA201004050\nversion,2\ninfo,visteam,MIN\nplay,1,0,spand001,32,CBBFBX,H/F7#\ncom,\"H/F7\"\n";
+        let formatted = EventFileFormatter::new().format(file).unwrap();
+        assert_eq!(formatted, "id,ANA201004050\nversion,2\ninfo,visteam,MIN\nplay,1,0,spand001,32,CBBFBX,HR/F7#\ncom,\"H/F7\"\n");
+    }
+
+    #[test]
+    fn check_reports_non_canonical_lines() {
+        let formatter = EventFileFormatter::new();
+        let file = "id,ANA201004050\nplay,1,0,spand001,00,X,S7/L7\nplay,1,0,hudso001,00,X,D7/7/L.1-H(NORBI)\n";
+
+        let lines = formatter.check(file).unwrap();
+        assert_eq!(lines, vec![NonCanonicalLine {
+            line_number: 3,
+            line: "play,1,0,hudso001,00,X,D7/7/L.1-H(NORBI)".to_string(),
+            canonical: "play,1,0,hudso001,00,X,D7/L7.1-H(NR)".to_string(),
+        }]);
+
+        assert!(formatter.check(&formatter.format(file).unwrap()).unwrap().is_empty());
+        assert!(formatter.check("play,1,0,spand001,00,X,Q").unwrap_err().starts_with("line 1:"));
+    }
+}
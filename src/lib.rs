@@ -0,0 +1,14 @@
+#![recursion_limit = "256"]
+
+pub mod box_score;
+pub mod chadwick;
+pub mod earned_runs;
+pub mod export;
+#[cfg(test)]
+mod fixtures;
+pub mod formatter;
+pub mod parser;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod writer;
@@ -1,9 +1,5 @@
-#![recursion_limit = "256"]
-
-mod parser;
-
-use parser::FileParser;
+use rstrosheet::parser::FileParser;
 
 fn main() {
-    let mut file_parser = FileParser::new();
+    let _file_parser = FileParser::new();
 }
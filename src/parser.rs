@@ -1,21 +1,16 @@
-mod game;
+pub mod game;
+pub mod roster;
+mod state;
 
 use chumsky::prelude::*;
 use strum::VariantNames;
-use strum_macros::{EnumString};
 use std::collections::HashSet;
-use game::{Advance, AdvanceParameter, AttendanceInfo, BallPathNode, Base, Count, DayNightInfo, Event, EventModifier, EventType, FieldConditionInfo, FieldLocation, Fielder, FieldingErrorType, Game, GameBuilder, GameTypeInfo, Pitch, PitchModifier, PitchType, PitchesInfo, Play, PlayNote, Player, Position, PrecipitationInfo, Runner, SkyInfo, TemperatureInfo, TimeOfGameInfo, WindDirectionInfo, WindSpeedInfo};
-
-#[derive(Debug, PartialEq, Clone, EnumString)]
-enum Team {
-    #[strum(serialize = "0")]
-    Visiting,
-    #[strum(serialize = "1")]
-    Home,
-}
+use game::{Adjustment, Advance, AdvanceParameter, AttendanceInfo, BallPathNode, Base, BattedBall, Count, DayNightInfo, Event, EventModifier, EventType, FieldConditionInfo, FieldLocation, Fielder, FieldingErrorType, Game, GameBuilder, GameTypeInfo, Hand, LineupEntry, Pitch, PitchModifier, PitchType, PitchesInfo, Play, PlayNote, Player, Position, PrecipitationInfo, Runner, SkyInfo, Team, TemperatureInfo, TimeOfGameInfo, Trajectory, WindDirectionInfo, WindSpeedInfo};
+use state::GameState;
 
 #[derive(Debug, PartialEq, Clone)]
-enum Info {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Info {
     VisitingTeam(String),
     HomeTeam(String),
     Date(String),
@@ -52,7 +47,7 @@ enum Info {
 }
 
 #[derive(Debug, PartialEq)]
-enum Line {
+pub(crate) enum Line {
     Id(String),
     Version(u8),
     StartSub {
@@ -71,8 +66,15 @@ enum Line {
         count: Count,
         pitches: Option<Vec<Pitch>>,
         event: Event,
+        event_text: String,
         note: Option<PlayNote>,
     },
+    EarnedRuns {
+        player_id: String,
+        earned_runs: u8,
+    },
+    Adjustment(Adjustment),
+    Comment(String),
 }
 
 impl Line {
@@ -101,7 +103,7 @@ impl Line {
     fn parse_id<'a>() -> impl Parser<'a, &'a str, Self, extra::Err<Rich<'a, char>>> {
         just("id,")
             .ignore_then(any().repeated().at_least(1).collect::<String>())
-            .map(|id| Self::Id(id))
+            .map(Self::Id)
     }
 
     fn parse_version<'a>() -> impl Parser<'a, &'a str, Self, extra::Err<Rich<'a, char>>> {
@@ -117,7 +119,7 @@ impl Line {
             .then_ignore(just(","))
             .then(Self::any_one_or_more())
             .then_ignore(just(","))
-            .then(Self::any_one_or_more())
+            .then(Self::any_one_or_more().map(|name| name.trim_matches('"').to_string()))
             .then_ignore(just(","))
             .then(one_of("01").map(|c: char| c.to_string().parse::<Team>().unwrap()))
             .then_ignore(just(","))
@@ -359,12 +361,20 @@ impl Line {
             .or(just("U").to(Fielder::Unknown))
     }
 
+    fn fielders<'a>() -> impl Parser<'a, &'a str, Vec<Fielder>, extra::Err<Rich<'a, char>>> {
+        Self::fielder()
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<Fielder>>()
+    }
+
     fn runner<'a>() -> impl Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>> {
         one_of(Runner::VARIANTS.concat())
     }
 
     fn base<'a>() -> impl Parser<'a, &'a str, Base, extra::Err<Rich<'a, char>>> {
-        one_of(Base::VARIANTS.concat())
+        // `VARIANTS` only holds one serialisation per variant, so home's "H" is listed here
+        one_of("123HB")
             .map(|c: char| c.to_string().parse::<Base>().unwrap())
     }
 
@@ -403,9 +413,14 @@ impl Line {
     }
 
     fn ball_path<'a>() -> impl Parser<'a, &'a str, Vec<BallPathNode>, extra::Err<Rich<'a, char>>> {
+        Self::ball_path_body()
+            .delimited_by(just("("), just(")"))
+    }
+
+    fn ball_path_body<'a>() -> impl Parser<'a, &'a str, Vec<BallPathNode>, extra::Err<Rich<'a, char>>> {
         let throwing_modifier = just("/TH")
             .ignore_then(Self::base().or_not())
-            .map(|base| FieldingErrorType::ThrowingError(base));
+            .map(FieldingErrorType::ThrowingError);
 
         let success_node = Self::fielder()
             .map(|fielder| BallPathNode::Success { fielder });
@@ -425,7 +440,7 @@ impl Line {
                 }
             });
 
-        let path = success_node
+        success_node
             .repeated()
             .collect::<Vec<BallPathNode>>()
             .then(error_node.or_not())
@@ -436,9 +451,7 @@ impl Line {
                 }
 
                 ball_path
-            });
-
-        path.delimited_by(just("("), just(")"))
+            })
     }
 
     fn caught_stealing<'a>() -> impl Parser<'a, &'a str, EventType, extra::Err<Rich<'a, char>>> {
@@ -496,43 +509,28 @@ impl Line {
     }
 
     fn event_type<'a>() -> impl Parser<'a, &'a str, EventType, extra::Err<Rich<'a, char>>> {
-        let out = Self::fielder()
-            .repeated()
-            .collect::<Vec<Fielder>>()
-            .then(Self::fielder())
+        let out = Self::fielders()
             .then(Self::parenthesised_runner().or_not())
-            .map(|((assisting_fielders, credited_fielder), runner)| {
-                let runner_out = match runner {
-                    Some(runner) => runner,
-                    None => Runner::from_fielder(&credited_fielder).unwrap(),
-                };
+            .map(|(mut fielders, runner)| {
+                let credited_fielder = fielders.pop().unwrap();
 
                 EventType::Out {
                     credited_fielder,
-                    assisting_fielders,
-                    runner_out,
+                    assisting_fielders: fielders,
+                    runner_out: runner.unwrap_or(Runner::Batter),
                 }
             });
 
-        let double_play = Self::fielder()
-            .repeated()
-            .collect::<Vec<Fielder>>()
-            .then(Self::fielder())
+        let double_play = Self::fielders()
             .then(Self::parenthesised_runner())
-            .then(Self::fielder()
-                .repeated()
-                .collect::<Vec<Fielder>>())
-            .then(Self::fielder())
+            .then(Self::fielders())
             .then(Self::parenthesised_runner().or_not())
-            .map(|(((((assisting_fielders_1, credited_fielder_1), runner_1), assisting_fielders_2), credited_fielder_2), runner_2)| {
-                let assisting_fielders = [assisting_fielders_1, assisting_fielders_2].concat();
-
-                let mut runners_out = vec![runner_1];
-                if let Some(runner_2) = runner_2 {
-                    runners_out.push(runner_2);
-                } else {
-                    runners_out.push(Runner::from_fielder(&credited_fielder_2).unwrap());
-                }
+            .map(|(((mut fielders_1, runner_1), mut fielders_2), runner_2)| {
+                let credited_fielder_1 = fielders_1.pop().unwrap();
+                let credited_fielder_2 = fielders_2.pop().unwrap();
+                let assisting_fielders = [fielders_1, fielders_2].concat();
+
+                let runners_out = vec![runner_1, runner_2.unwrap_or(Runner::Batter)];
 
                 let credited_fielders = vec![credited_fielder_1, credited_fielder_2];
 
@@ -543,30 +541,19 @@ impl Line {
                 }
             });
 
-        let triple_play = Self::fielder()
-            .repeated()
-            .collect::<Vec<Fielder>>()
-            .then(Self::fielder())
+        let triple_play = Self::fielders()
             .then(Self::parenthesised_runner())
-            .then(Self::fielder()
-                .repeated()
-                .collect::<Vec<Fielder>>()
-                .then(Self::fielder())
+            .then(Self::fielders()
                 .then(Self::parenthesised_runner()))
-            .then(Self::fielder()
-                .repeated()
-                .collect::<Vec<Fielder>>()
-                .then(Self::fielder())
+            .then(Self::fielders()
                 .then(Self::parenthesised_runner().or_not()))
-            .map(|((((assisting_fielders_1, credited_fielder_1), runner_1), ((assisting_fielders_2, credited_fielder_2), runner_2)), ((assisting_fielders_3, credited_fielder_3), runner_3))| {
-                let assisting_fielders = [assisting_fielders_1, assisting_fielders_2, assisting_fielders_3].concat();
-
-                let mut runners_out = vec![runner_1, runner_2];
-                if let Some(runner_3) = runner_3 {
-                    runners_out.push(runner_3);
-                } else {
-                    runners_out.push(Runner::from_fielder(&credited_fielder_3).unwrap());
-                }
+            .map(|(((mut fielders_1, runner_1), (mut fielders_2, runner_2)), (mut fielders_3, runner_3))| {
+                let credited_fielder_1 = fielders_1.pop().unwrap();
+                let credited_fielder_2 = fielders_2.pop().unwrap();
+                let credited_fielder_3 = fielders_3.pop().unwrap();
+                let assisting_fielders = [fielders_1, fielders_2, fielders_3].concat();
+
+                let runners_out = vec![runner_1, runner_2, runner_3.unwrap_or(Runner::Batter)];
 
                 let credited_fielders = vec![credited_fielder_1, credited_fielder_2, credited_fielder_3];
 
@@ -584,42 +571,30 @@ impl Line {
             .ignore_then(Self::fielder()
                 .repeated()
                 .collect::<Vec<Fielder>>())
-            .then(Self::fielder().or_not())
-            .map(|(assisting_fielders, credited_fielder)| {
-                let credited_fielder = match credited_fielder {
-                    Some(credited_fielder) => credited_fielder,
-                    None => Fielder::Unknown,
-                };
+            .map(|mut fielders| {
+                let credited_fielder = fielders.pop().unwrap_or(Fielder::Unknown);
 
-                EventType::Single { credited_fielder, assisting_fielders }
+                EventType::Single { credited_fielder, assisting_fielders: fielders }
             });
 
         let double = just("D")
             .ignore_then(Self::fielder()
                 .repeated()
                 .collect::<Vec<Fielder>>())
-            .then(Self::fielder().or_not())
-            .map(|(assisting_fielders, credited_fielder)| {
-                let credited_fielder = match credited_fielder {
-                    Some(credited_fielder) => credited_fielder,
-                    None => Fielder::Unknown,
-                };
+            .map(|mut fielders| {
+                let credited_fielder = fielders.pop().unwrap_or(Fielder::Unknown);
 
-                EventType::Double { credited_fielder, assisting_fielders }
+                EventType::Double { credited_fielder, assisting_fielders: fielders }
             });
 
         let triple = just("T")
             .ignore_then(Self::fielder()
                 .repeated()
                 .collect::<Vec<Fielder>>())
-            .then(Self::fielder().or_not())
-            .map(|(assisting_fielders, credited_fielder)| {
-                let credited_fielder = match credited_fielder {
-                    Some(credited_fielder) => credited_fielder,
-                    None => Fielder::Unknown,
-                };
+            .map(|mut fielders| {
+                let credited_fielder = fielders.pop().unwrap_or(Fielder::Unknown);
 
-                EventType::Triple { credited_fielder, assisting_fielders }
+                EventType::Triple { credited_fielder, assisting_fielders: fielders }
             });
 
         let ground_rule_double = just("DGR")
@@ -633,12 +608,12 @@ impl Line {
             .ignore_then(Self::fielder())
             .map(|credited_fielder| EventType::ErrorOnFoulFlyBall { credited_fielder });
 
-        let solo_home_run = just("H")
-            .or(just("HR"))
+        let solo_home_run = just("HR")
+            .or(just("H"))
             .to(EventType::SoloHomeRun);
 
-        let inside_the_park_home_run = just("H")
-            .or(just("HR"))
+        let inside_the_park_home_run = just("HR")
+            .or(just("H"))
             .ignore_then(Self::fielder())
             .map(|credited_fielder| EventType::InsideTheParkHomeRun { credited_fielder });
 
@@ -664,8 +639,8 @@ impl Line {
         let no_play = just("NP")
             .to(EventType::NoPlay);
 
-        let walk = just("I")
-            .or(just("IW"))
+        let walk = just("IW")
+            .or(just("I"))
             .or(just("W"))
             .then(just("+")
                 .ignore_then(Self::strikeout_or_walk_event())
@@ -681,45 +656,43 @@ impl Line {
         let defensive_indifference = just("DI")
             .to(EventType::DefensiveIndifference);
 
-        let pickoff = Self::pickoff_no_caught_stealing()
-            .or(Self::pickoff_caught_stealing());
+        let pickoff = Self::pickoff_caught_stealing()
+            .or(Self::pickoff_no_caught_stealing());
 
-        out
-            .or(double_play)
+        // alternatives sharing a prefix are ordered longest first, since a shorter match
+        // is never backtracked out of once it succeeds
+        Self::error()
             .or(triple_play)
+            .or(double_play)
+            .or(out)
+            .or(Self::caught_stealing())
             .or(interference)
+            .or(Self::stolen_base())
             .or(single)
+            .or(ground_rule_double)
+            .or(defensive_indifference)
             .or(double)
             .or(triple)
-            .or(ground_rule_double)
-            .or(Self::error())
             .or(fielders_choice)
             .or(error_on_foul_fly_ball)
-            .or(solo_home_run)
-            .or(inside_the_park_home_run)
             .or(hit_by_pitch)
+            .or(inside_the_park_home_run)
+            .or(solo_home_run)
             .or(strikeout)
             .or(no_play)
+            .or(Self::wild_pitch())
             .or(walk)
             .or(balk)
-            .or(Self::caught_stealing())
-            .or(defensive_indifference)
             .or(Self::other_advance())
             .or(Self::passed_ball())
-            .or(Self::wild_pitch())
             .or(pickoff)
-            .or(Self::stolen_base())
     }
 
     fn event_modifier<'a>() -> impl Parser<'a, &'a str, EventModifier, extra::Err<Rich<'a, char>>> {
         let appeal_play = just("AP")
             .to(EventModifier::AppealPlay);
 
-        let pop_up_bunt = just("BP")
-            .to(EventModifier::PopUpBunt);
 
-        let ground_ball_bunt = just("BG")
-            .to(EventModifier::GroundBallBunt);
 
         let bunt_grounded_into_double_play = just("BGDP")
             .to(EventModifier::BuntGroundedIntoDoublePlay);
@@ -727,8 +700,6 @@ impl Line {
         let batter_interference = just("BINT")
             .to(EventModifier::BatterInterference);
 
-        let line_drive_bunt = just("BL")
-            .to(EventModifier::LineDriveBunt);
 
         let batting_out_of_turn = just("BOOT")
             .to(EventModifier::BattingOutOfTurn);
@@ -756,10 +727,8 @@ impl Line {
 
         let error = just("E")
             .ignore_then(Self::fielder())
-            .map(|fielder| EventModifier::Error(fielder));
+            .map(EventModifier::Error);
 
-        let fly = just("F")
-            .to(EventModifier::Fly);
 
         let fly_ball_double_play = just("FDP")
             .to(EventModifier::FlyBallDoublePlay);
@@ -773,8 +742,6 @@ impl Line {
         let force_out = just("FO")
             .to(EventModifier::ForceOut);
 
-        let ground_ball = just("G")
-            .to(EventModifier::GroundBall);
 
         let ground_ball_double_play = just("GDP")
             .to(EventModifier::GroundBallDoublePlay);
@@ -791,8 +758,6 @@ impl Line {
         let inside_the_park_home_run = just("IPHR")
             .to(EventModifier::InsideTheParkHomeRun);
 
-        let line_drive = just("L")
-            .to(EventModifier::LineDrive);
 
         let lined_into_double_play = just("LDP")
             .to(EventModifier::LinedIntoDoublePlay);
@@ -809,15 +774,13 @@ impl Line {
         let fielder_obstructing_runner = just("OBS")
             .to(EventModifier::FielderObstructingRunner);
 
-        let pop_fly = just("P")
-            .to(EventModifier::PopFly);
 
         let runner_passed_another_runner_and_was_called_out = just("PR")
             .to(EventModifier::RunnerPassedAnotherRunnerAndWasCalledOut);
 
         let relay_throw_from_fielder_with_no_out = just("R")
             .ignore_then(Self::fielder())
-            .map(|fielder| EventModifier::RelayThrowFromFielderWithNoOut(fielder));
+            .map(EventModifier::RelayThrowFromFielderWithNoOut);
 
         let runner_interference = just("RINT")
             .to(EventModifier::RunnerInterference);
@@ -830,7 +793,7 @@ impl Line {
         
         let throw = just("TH")
             .ignore_then(Self::base().or_not())
-            .map(|fielder| EventModifier::Throw(fielder));
+            .map(EventModifier::Throw);
         
         let unspecified_triple_play = just("TP")
             .to(EventModifier::UnspecifiedTriplePlay);
@@ -841,7 +804,15 @@ impl Line {
         let umpire_review_of_call_on_the_field = just("UREV")
             .to(EventModifier::UmpireReviewOfCallOnTheField);
 
-        let hit_location = one_of("123456789")
+        let trajectory = just("BG").to(Trajectory::GroundBallBunt)
+            .or(just("BL").to(Trajectory::LineDriveBunt))
+            .or(just("BP").to(Trajectory::PopUpBunt))
+            .or(just("G").to(Trajectory::GroundBall))
+            .or(just("L").to(Trajectory::LineDrive))
+            .or(just("F").to(Trajectory::Fly))
+            .or(just("P").to(Trajectory::PopFly));
+
+        let location = one_of("123456789")
             .repeated()
             .at_least(1)
             .collect::<String>()
@@ -849,49 +820,56 @@ impl Line {
                 .repeated()
                 .collect::<String>())
             .map(|(a, b)| [a, b].concat())
-            .map(|hit_location| EventModifier::HitLocation(hit_location.parse().unwrap()));
+            .try_map(|location, span| location
+                .parse::<FieldLocation>()
+                .map_err(|_| Rich::custom(span, format!("unknown location {location}"))));
 
-        let event_modifier = hit_location
+        // the trajectory and location are one token, so `/G/56` stays two batted balls and
+        // `/56G` is rejected rather than read as a location followed by a trajectory
+        let batted_ball = trajectory
+            .or_not()
+            .then(location.or_not())
+            .then_ignore(any().filter(|c: &char| c.is_ascii_alphanumeric()).not())
+            .try_map(|(trajectory, location), span| match (trajectory, location) {
+                (None, None) => Err(Rich::custom(span, "expected a trajectory or location")),
+                (trajectory, location) => Ok(EventModifier::BattedBall(BattedBall { trajectory, location })),
+            });
+
+        // as with event types, modifiers sharing a prefix are ordered longest first
+        let event_modifier = batted_ball
+            .or(courtesy_batter)
+            .or(courtesy_fielder)
+            .or(courtesy_runner)
             .or(called_third_strike)
-            .or(fly)
-            .or(ground_ball)
-            .or(line_drive)
-            .or(pop_fly)
+            .or(fly_ball_double_play)
+            .or(fan_interference)
             .or(appeal_play)
-            .or(pop_up_bunt)
-            .or(ground_ball_bunt)
-            .or(line_drive_bunt)
+            .or(bunt_grounded_into_double_play)
+            .or(bunt_popped_into_double_play)
+            .or(batter_interference)
+            .or(batting_out_of_turn)
             .or(runner_hit_by_batted_ball)
             .or(unspecified_double_play)
             .or(error)
             .or(foul)
             .or(force_out)
-            .or(infield_fly_rule)
-            .or(relay_throw_from_fielder_with_no_out)
-            .or(sacrifice_fly)
-            .or(sacrifice_hit_or_bunt)
-            .or(throw)
-            .or(unspecified_triple_play)
-            .or(fly_ball_double_play)
             .or(ground_ball_double_play)
             .or(ground_ball_triple_play)
+            .or(infield_fly_rule)
             .or(interference)
+            .or(inside_the_park_home_run)
             .or(lined_into_double_play)
             .or(lined_into_triple_play)
+            .or(manager_challenge_of_call_on_the_field)
             .or(no_double_play_credited_for_this_play)
             .or(fielder_obstructing_runner)
-            .or(bunt_grounded_into_double_play)
-            .or(batter_interference)
-            .or(batting_out_of_turn)
-            .or(bunt_popped_into_double_play)
-            .or(courtesy_batter)
-            .or(courtesy_fielder)
-            .or(courtesy_runner)
-            .or(fan_interference)
-            .or(inside_the_park_home_run)
-            .or(manager_challenge_of_call_on_the_field)
             .or(runner_passed_another_runner_and_was_called_out)
             .or(runner_interference)
+            .or(relay_throw_from_fielder_with_no_out)
+            .or(sacrifice_fly)
+            .or(sacrifice_hit_or_bunt)
+            .or(throw)
+            .or(unspecified_triple_play)
             .or(umpire_interference)
             .or(umpire_review_of_call_on_the_field);
 
@@ -906,8 +884,8 @@ impl Line {
             .or(just("X")
                 .to(true));
 
-        let ball_path = Self::ball_path()
-            .map(|ball_path| AdvanceParameter::BallPath(ball_path));
+        let ball_path = Self::ball_path_body()
+            .map(AdvanceParameter::BallPath);
 
         let unearned = just("UR")
             .to(AdvanceParameter::Unearned);
@@ -922,12 +900,12 @@ impl Line {
             .or(just("NORBI"))
             .to(AdvanceParameter::RBINotCredited);
 
-        let position = one_of('1'..'9')
-            .map(|c: char| c.to_string().parse::<FieldLocation>().unwrap());
-
-        let interference = position
+        let interference = one_of('1'..='9')
             .then_ignore(just("/INT"))
-            .map(|position| AdvanceParameter::Interference(position));
+            .try_map(|c: char, span| c.to_string()
+                .parse::<FieldLocation>()
+                .map_err(|_| Rich::custom(span, format!("unknown location {c}"))))
+            .map(AdvanceParameter::Interference);
 
         let wild_pitch = just("WP")
             .to(AdvanceParameter::WildPitch);
@@ -935,14 +913,15 @@ impl Line {
         let passed_ball = just("PB")
             .to(AdvanceParameter::PassedBall);
 
-        let advance_parameter = ball_path
-            .or(unearned)
+        // a ball path can start with `U`, so it goes last to let "UR" match first
+        let advance_parameter = unearned
             .or(team_unearned)
             .or(rbi_credited)
             .or(rbi_not_credited)
             .or(wild_pitch)
             .or(passed_ball)
             .or(interference)
+            .or(ball_path)
             .delimited_by(just("("), just(")"));
 
         let advance_parameters = advance_parameter
@@ -1010,54 +989,113 @@ impl Line {
             .then_ignore(just(","))
             .then(pitches)
             .then_ignore(just(","))
-            .then(event)
+            .then(event.map_with(|event, e| (event, e.slice().to_string())))
             .then(one_of("#!?+-").map(|note: char| note.to_string().parse().unwrap()).or_not())
-            .map(|((((((inning, team), batter_id), count), pitches), event), note)| Self::Play {
+            .map(|((((((inning, team), batter_id), count), pitches), (event, event_text)), note)| Self::Play {
                 inning,
                 team,
                 batter_id,
                 count,
                 pitches,
                 event,
+                event_text,
                 note,
             })
     }
 
+    fn parse_earned_runs<'a>() -> impl Parser<'a, &'a str, Self, extra::Err<Rich<'a, char>>> {
+        just("data,er,")
+            .ignore_then(Self::any_one_or_more())
+            .then_ignore(just(","))
+            .then(Self::number())
+            .map(|(player_id, earned_runs)| Self::EarnedRuns {
+                player_id,
+                earned_runs: earned_runs as u8,
+            })
+    }
+
+    fn parse_adjustment<'a>() -> impl Parser<'a, &'a str, Self, extra::Err<Rich<'a, char>>> {
+        let hand = one_of("LR")
+            .map(|c: char| c.to_string().parse::<Hand>().unwrap());
+
+        let runner = just("radj,")
+            .ignore_then(Self::any_one_or_more().then_ignore(just(",")))
+            .then(one_of("123").map(|c: char| c.to_string().parse::<Base>().unwrap()))
+            .map(|(player_id, base)| Adjustment::Runner { player_id, base });
+
+        let batting_hand = just("badj,")
+            .ignore_then(Self::any_one_or_more().then_ignore(just(",")))
+            .then(hand)
+            .map(|(player_id, hand)| Adjustment::BattingHand { player_id, hand });
+
+        let pitching_hand = just("padj,")
+            .ignore_then(Self::any_one_or_more().then_ignore(just(",")))
+            .then(hand)
+            .map(|(player_id, hand)| Adjustment::PitchingHand { player_id, hand });
+
+        let lineup = just("ladj,")
+            .ignore_then(one_of("01").map(|c: char| c.to_string().parse::<Team>().unwrap()))
+            .then_ignore(just(","))
+            .then(one_of('1'..='9').map(|c: char| c.to_digit(10).unwrap() as u8))
+            .map(|(team, batting_order)| Adjustment::Lineup { team, batting_order });
+
+        runner
+            .or(batting_hand)
+            .or(pitching_hand)
+            .or(lineup)
+            .map(Self::Adjustment)
+    }
+
+    fn parse_comment<'a>() -> impl Parser<'a, &'a str, Self, extra::Err<Rich<'a, char>>> {
+        just("com,")
+            .ignore_then(any().repeated().collect::<String>())
+            .map(|comment| Self::Comment(comment.trim_matches('"').to_string()))
+    }
+
     fn parse_internal<'a>() -> impl Parser<'a, &'a str, Self, extra::Err<Rich<'a, char>>> {
         Self::parse_id()
             .or(Self::parse_version())
             .or(Self::parse_start_sub())
             .or(Self::parse_info())
             .or(Self::parse_play())
+            .or(Self::parse_earned_runs())
+            .or(Self::parse_adjustment())
+            .or(Self::parse_comment())
     }
 
     pub fn parse(line: &str) -> Result<Self, String> {
-        let result = Self::parse_internal().parse(line);
-
-        let errors = result.errors().collect::<Vec<_>>();
-        if errors.is_empty() {
-            println!("No errors");
-        } else {
-            println!("{} errors", errors.len());
-            for error in errors {
-                println!("Error: {error}");
-            }
-        }
-
-        let result_output = result.into_output();
+        Self::parse_internal()
+            .parse(line)
+            .into_result()
+            .map_err(|errors| {
+                let errors = errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<String>>()
+                    .join("; ");
+
+                format!("Invalid line: {line} ({errors})")
+            })
+    }
 
-        result_output.ok_or(format!("Invalid line: {line}"))
+    pub(crate) fn parse_event_modifier(modifier: &str) -> Result<EventModifier, String> {
+        Self::event_modifier()
+            .parse(modifier)
+            .into_result()
+            .map_err(|_| format!("Invalid event modifier: {modifier}"))
     }
 }
 
 struct GameParser {
     game_builder: GameBuilder,
+    state: GameState,
 }
 
 impl GameParser {
     pub fn new() -> Self {
         Self {
             game_builder: GameBuilder::new(),
+            state: GameState::new(),
         }
     }
 
@@ -1090,10 +1128,20 @@ impl GameParser {
                     name: player_name.to_string(),
                     team: team_name.to_string(),
                     batting_order: *batting_order,
-                    positions: HashSet::from([fielding_position.clone()]),
+                    positions: HashSet::from([*fielding_position]),
                 };
                 
-                self.game_builder.set_player(&team, player.clone());
+                self.game_builder.set_player(team, player);
+                self.game_builder.lineup_entry(LineupEntry {
+                    is_start: *is_start,
+                    player_id: player_id.to_string(),
+                    player_name: player_name.to_string(),
+                    team: *team,
+                    batting_order: *batting_order,
+                    position: *fielding_position,
+                    play_index: self.game_builder.plays.len(),
+                });
+                self.state.substitute(team, player_id, *batting_order, *fielding_position);
 
                 Ok(())
             },
@@ -1101,14 +1149,58 @@ impl GameParser {
                 self.game_builder.set_info(info.clone());
                 Ok(())
             },
-            Line::Play { inning, team, batter_id, count, pitches, event, note } => todo!(),
+            Line::Play { inning, team, batter_id, count, pitches, event, event_text, note } => {
+                self.state.start_play(*inning, *team);
+
+                let defense = self.state.team(&team.opponent()).defense.clone();
+                let outs = self.state.outs;
+                let bases = self.state.bases.clone();
+                let runner_advances = self.state.apply(event, batter_id, defense.get(&Position::Pitcher))?;
+
+                let play = Play {
+                    info_state: self.game_builder.info_state.clone(),
+                    home_team_players: self.game_builder.home_team_players.clone(),
+                    visiting_team_players: self.game_builder.visiting_team_players.clone(),
+                    inning: *inning,
+                    team: *team,
+                    batter_id: batter_id.to_string(),
+                    count: Some(count.clone()),
+                    pitches: pitches.clone(),
+                    event: Some(event.clone()),
+                    event_text: event_text.to_string(),
+                    note: note.clone(),
+                    comments: Vec::new(),
+                    defense,
+                    outs,
+                    bases,
+                    runner_advances,
+                };
+
+                self.game_builder.play(play);
+
+                Ok(())
+            },
+            Line::EarnedRuns { player_id, earned_runs } => {
+                self.game_builder.earned_runs(player_id.to_string(), *earned_runs);
+                Ok(())
+            },
+            Line::Adjustment(adjustment) => {
+                if let Adjustment::Runner { player_id, base } = adjustment {
+                    self.state.place_runner(player_id, *base);
+                }
+                self.game_builder.adjustment(adjustment.clone());
+                Ok(())
+            },
+            Line::Comment(comment) => {
+                self.game_builder.comment(comment.to_string());
+                Ok(())
+            },
         }
     }
 
     pub fn parse_game(&mut self, game: &str) -> Result<Game, String> {
-        let lines = game.split("\n").collect::<Vec<&str>>();
-        for line in lines {
-            let line = Line::parse(line)?;
+        for line in game.lines().filter(|line| !line.trim().is_empty()) {
+            let line = Line::parse(line.trim_end())?;
             self.parse_line(&line)?;
         }
 
@@ -1118,6 +1210,12 @@ impl GameParser {
 
 pub struct FileParser;
 
+impl Default for FileParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FileParser {
     pub fn new() -> Self {
         Self {}
@@ -1127,6 +1225,7 @@ impl FileParser {
         // split into games, where each game begins with "id,"
         file_content
             .split("id,")
+            .filter(|s| !s.trim().is_empty())
             .map(|s| format!("id,{s}"))
             .collect::<Vec<String>>()
     }
@@ -1382,6 +1481,10 @@ mod tests {
         #[test]
         fn parse_info_fieldcond() {
             // test valid
+            let line = "info,fieldcond,damp";
+            let result = Line::parse(line);
+            assert_eq!(result, Ok(Line::Info(Info::FieldCondition(FieldConditionInfo::Damp))));
+
             let line = "info,fieldcond,dry";
             let result = Line::parse(line);
             assert_eq!(result, Ok(Line::Info(Info::FieldCondition(FieldConditionInfo::Dry))));
@@ -1692,6 +1795,37 @@ mod tests {
             assert!(result.is_err());
         }
 
+        #[test]
+        fn parse_earned_runs_line() {
+            let line = "data,er,rijoj001,2";
+            let result = Line::parse(line);
+            assert_eq!(result, Ok(Line::EarnedRuns {
+                player_id: "rijoj001".to_string(),
+                earned_runs: 2,
+            }));
+        }
+
+        #[test]
+        fn parse_adjustment_lines() {
+            assert_eq!(Line::parse("radj,youne003,2"), Ok(Line::Adjustment(Adjustment::Runner {
+                player_id: "youne003".to_string(),
+                base: Base::Second,
+            })));
+            assert_eq!(Line::parse("badj,bonib001,R"), Ok(Line::Adjustment(Adjustment::BattingHand {
+                player_id: "bonib001".to_string(),
+                hand: Hand::Right,
+            })));
+            assert_eq!(Line::parse("padj,harrg001,L"), Ok(Line::Adjustment(Adjustment::PitchingHand {
+                player_id: "harrg001".to_string(),
+                hand: Hand::Left,
+            })));
+            assert_eq!(Line::parse("ladj,0,4"), Ok(Line::Adjustment(Adjustment::Lineup {
+                team: Team::Visiting,
+                batting_order: 4,
+            })));
+            assert!(Line::parse("radj,youne003,H").is_err());
+        }
+
         #[test]
         fn parse_start_sub_line() {
             let line = "start,1,player1,0,1,3";
@@ -1716,6 +1850,20 @@ mod tests {
                 position: Position::CenterField,
             }));
         }
+
+        #[test]
+        fn parse_comment_line() {
+            let line = "com,\"Span left the game with a sore hamstring\"";
+            let result = Line::parse(line);
+            assert_eq!(result, Ok(Line::Comment("Span left the game with a sore hamstring".to_string())));
+        }
+
+        #[test]
+        fn parse_quoted_player_name() {
+            let line = "start,spand001,\"Denard Span\",0,1,8";
+            let result = Line::parse(line);
+            assert!(matches!(result, Ok(Line::StartSub { player_name, .. }) if player_name == "Denard Span"));
+        }
     
         #[test]
         fn parse_play_line() {
@@ -1743,18 +1891,142 @@ mod tests {
                         runner_out: Runner::Batter,
                     },
                     modifiers: vec![
-                        EventModifier::Fly,
-                        EventModifier::HitLocation(FieldLocation::CenterLeft),
+                        EventModifier::BattedBall(BattedBall {
+                            trajectory: Some(Trajectory::Fly),
+                            location: Some(FieldLocation::CenterLeft),
+                        }),
                     ],
                     advances: Vec::new(),
                 },
+                event_text: "8/F78".to_string(),
                 note: None,
             }));
         }
     }
 
+    mod event {
+        use super::*;
+        use crate::parser::game::{BattedBall, Trajectory};
+
+        fn parse_event(line: &str) -> Event {
+            match Line::parse(line) {
+                Ok(Line::Play { event, .. }) => event,
+                other => panic!("expected a play, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn parse_outs_and_double_plays() {
+            let event = parse_event("play,1,0,abc,??,,64(1)3/GDP");
+            assert_eq!(event.event_type, EventType::DoublePlay {
+                credited_fielders: vec![Fielder::Known(4), Fielder::Known(3)],
+                assisting_fielders: vec![Fielder::Known(6)],
+                runners_out: vec![Runner::First, Runner::Batter],
+            });
+            assert_eq!(event.modifiers, vec![EventModifier::GroundBallDoublePlay]);
+
+            let event = parse_event("play,1,0,abc,??,,54(1)/FO/G6.B-1");
+            assert_eq!(event.event_type, EventType::Out {
+                credited_fielder: Fielder::Known(4),
+                assisting_fielders: vec![Fielder::Known(5)],
+                runner_out: Runner::First,
+            });
+            assert_eq!(event.modifiers, vec![
+                EventModifier::ForceOut,
+                EventModifier::BattedBall(BattedBall {
+                    trajectory: Some(Trajectory::GroundBall),
+                    location: Some(FieldLocation::Shortstop),
+                }),
+            ]);
+        }
+
+        #[test]
+        fn parse_events_sharing_a_prefix() {
+            assert_eq!(parse_event("play,1,0,abc,??,,S8/G").event_type, EventType::Single {
+                credited_fielder: Fielder::Known(8),
+                assisting_fielders: Vec::new(),
+            });
+            assert_eq!(parse_event("play,1,0,abc,??,,SB2").event_type, EventType::StolenBase {
+                bases: vec![Base::Second],
+            });
+            assert_eq!(parse_event("play,1,0,abc,??,,HR/F78").event_type, EventType::SoloHomeRun);
+            assert_eq!(parse_event("play,1,0,abc,??,,HP").event_type, EventType::HitByPitch);
+            assert_eq!(parse_event("play,1,0,abc,??,,DGR").event_type, EventType::GroundRuleDouble);
+            assert_eq!(parse_event("play,1,0,abc,??,,WP.2-3").event_type, EventType::WildPitch);
+            assert_eq!(parse_event("play,1,0,abc,??,,IW").event_type, EventType::Walk {
+                intentional: true,
+                base_running_event: Box::new(None),
+            });
+            assert_eq!(parse_event("play,1,0,abc,??,,CS2(24)").event_type, EventType::CaughtStealing {
+                base: Base::Second,
+                ball_path: vec![
+                    BallPathNode::Success { fielder: Fielder::Known(2) },
+                    BallPathNode::Success { fielder: Fielder::Known(4) },
+                ],
+            });
+            assert_eq!(parse_event("play,1,0,abc,??,,8/FL").modifiers, vec![EventModifier::Foul]);
+        }
+
+        #[test]
+        fn parse_locations_without_panicking() {
+            assert_eq!(parse_event("play,1,0,abc,??,,53/G5").modifiers, vec![
+                EventModifier::BattedBall(BattedBall {
+                    trajectory: Some(Trajectory::GroundBall),
+                    location: Some(FieldLocation::ThirdBase),
+                }),
+            ]);
+            assert_eq!(parse_event("play,1,0,abc,??,,S7.B-1(5/INT)").advances[0].parameters, vec![
+                AdvanceParameter::Interference(FieldLocation::ThirdBase),
+            ]);
+            assert!(Line::parse("play,1,0,abc,??,,S5/G59").is_err());
+        }
+
+        #[test]
+        fn parse_combined_batted_balls() {
+            let batted_ball = |trajectory, location: &str| BattedBall {
+                trajectory: Some(trajectory),
+                location: Some(location.parse().unwrap()),
+            };
+
+            assert_eq!(parse_event("play,1,0,abc,??,,S5/G56").get_batted_ball(), Some(&batted_ball(Trajectory::GroundBall, "56")));
+            assert_eq!(parse_event("play,1,0,abc,??,,S9/L9S").get_batted_ball(), Some(&batted_ball(Trajectory::LineDrive, "9S")));
+            assert_eq!(parse_event("play,1,0,abc,??,,8/F78XD").get_batted_ball(), Some(&batted_ball(Trajectory::Fly, "78XD")));
+            assert_eq!(parse_event("play,1,0,abc,??,,53/G5").get_batted_ball(), Some(&batted_ball(Trajectory::GroundBall, "5")));
+            assert_eq!(parse_event("play,1,0,abc,??,,S8/G").get_batted_ball(), Some(&BattedBall {
+                trajectory: Some(Trajectory::GroundBall),
+                location: None,
+            }));
+            assert_eq!(parse_event("play,1,0,abc,??,,K").get_batted_ball(), None);
+
+            for location in FieldLocation::VARIANTS {
+                for trajectory in ["G", "L", "F", "P", "BG", "BL", "BP"] {
+                    let event = parse_event(&format!("play,1,0,abc,??,,HR/{trajectory}{location}"));
+                    let batted_ball = event.get_batted_ball().unwrap();
+                    assert_eq!(batted_ball.to_string(), format!("{trajectory}{location}"));
+                    assert_eq!(event.to_string(), format!("HR/{trajectory}{location}"));
+                }
+            }
+
+            assert!(Line::parse("play,1,0,abc,??,,S5/G59").is_err());
+            assert!(Line::parse("play,1,0,abc,??,,8/F8XL").is_err());
+            assert!(Line::parse("play,1,0,abc,??,,S8/56G").is_err());
+        }
+
+        #[test]
+        fn keep_separate_trajectory_and_location() {
+            let event = parse_event("play,1,0,abc,??,,S6/G/56");
+            assert_eq!(event.modifiers, vec![
+                EventModifier::BattedBall(BattedBall { trajectory: Some(Trajectory::GroundBall), location: None }),
+                EventModifier::BattedBall(BattedBall { trajectory: None, location: Some(FieldLocation::ThirdShortstop) }),
+            ]);
+            assert_eq!(event.get_batted_ball().unwrap().location, None);
+            assert_eq!(event.to_string(), "S6/G/56");
+        }
+    }
+
     mod game_parser {
         use super::*;
+        use game::{AdjustmentEntry, BaseRunner, PitcherResponsibility, RunnerAdvance};
 
         #[test]
         fn parse_id_line() {
@@ -1857,5 +2129,177 @@ mod tests {
             assert_eq!(parser.game_builder.home_team_players.len(), 1);
             assert_eq!(parser.game_builder.visiting_team_players.len(), 1);
         }
+
+        #[test]
+        fn resolve_fielders_to_player_ids() {
+            let mut parser = GameParser::new();
+
+            let game = "id,CIN199004090
+version,2
+info,visteam,HOU
+info,hometeam,CIN
+start,biggc001,\"Craig Biggio\",0,1,2
+start,rijoj001,\"Jose Rijo\",1,0,1
+start,sabob001,\"Chris Sabo\",1,5,5
+start,larkb001,\"Barry Larkin\",1,2,6
+start,benzt001,\"Todd Benzinger\",1,3,3
+play,1,0,biggc001,01,CX,63/G
+sub,duncm001,\"Mariano Duncan\",1,5,5
+play,1,0,biggc001,00,X,5/L";
+            let game = parser.parse_game(game).unwrap();
+            let plays = game.get_plays();
+            assert_eq!(plays.len(), 2);
+
+            let play = &plays[0];
+            let Some(Event { event_type: EventType::Out { credited_fielder, assisting_fielders, runner_out }, .. }) = play.get_event() else {
+                panic!("expected an out");
+            };
+            assert_eq!(runner_out, &Runner::Batter);
+            assert_eq!(play.fielder_id(credited_fielder), Some(&"benzt001".to_string()));
+            assert_eq!(play.fielder_id(&assisting_fielders[0]), Some(&"larkb001".to_string()));
+            assert_eq!(play.get_pitcher_id(), Some(&"rijoj001".to_string()));
+            assert_eq!(play.fielder_id(&Fielder::Known(5)), Some(&"sabob001".to_string()));
+
+            // the substitution is reflected in the following play only
+            let play = &plays[1];
+            assert_eq!(play.fielder_id(&Fielder::Known(5)), Some(&"duncm001".to_string()));
+            assert_eq!(play.fielder_id(&Fielder::Known(7)), None);
+            assert_eq!(play.fielder_id(&Fielder::Unknown), None);
+        }
+
+        #[test]
+        fn resolve_runner_advances_to_player_ids() {
+            let mut parser = GameParser::new();
+
+            let game = "id,CIN199004090
+version,2
+info,visteam,HOU
+info,hometeam,CIN
+start,youngg001,\"Gerald Young\",0,1,8
+start,biggc001,\"Craig Biggio\",0,2,2
+start,davig001,\"Glenn Davis\",0,3,3
+start,rijoj001,\"Jose Rijo\",1,0,1
+play,1,0,youngg001,00,X,S7
+play,1,0,biggc001,30,BBBB,W.1-2
+sub,ramir001,\"Rafael Ramirez\",0,1,12
+play,1,0,davig001,00,>B,SB3
+sub,myerr001,\"Randy Myers\",1,0,1
+play,1,0,davig001,10,X,D8/L.3-H;1-H
+play,1,0,davig001,00,,CS3(25)";
+            let game = parser.parse_game(game).unwrap();
+            let plays = game.get_plays();
+
+            let runner = |id: &str, pitcher_id: &str| BaseRunner {
+                id: id.to_string(),
+                pitcher_id: Some(pitcher_id.to_string()),
+                inherited_by: None,
+            };
+
+            assert_eq!(plays[0].get_runner_advances(), &vec![RunnerAdvance {
+                runner: runner("youngg001", "rijoj001"),
+                advance: Advance { starting_base: Base::Home, ending_base: Base::First, out: false, parameters: Vec::new() },
+            }]);
+
+            // the pinch runner replaces the runner on second, who stays charged to the starter
+            let play = &plays[2];
+            assert_eq!(play.get_runner(&Base::First), Some(&runner("biggc001", "rijoj001")));
+            assert_eq!(play.get_runner(&Base::Second), Some(&runner("ramir001", "rijoj001")));
+            assert_eq!(play.get_runner_advances().len(), 1);
+            assert_eq!(play.get_runner_advances()[0].runner, runner("ramir001", "rijoj001"));
+            assert_eq!(play.get_runner_advances()[0].advance.ending_base, Base::Third);
+
+            // runs driven in off the reliever are charged to the starter
+            let play = &plays[3];
+            assert_eq!(play.get_pitcher_id(), Some(&"myerr001".to_string()));
+            assert_eq!(play.get_runs(), 2);
+            let scored = play.get_runner_advances()
+                .iter()
+                .filter(|runner_advance| runner_advance.advance.is_run())
+                .map(|runner_advance| runner_advance.runner.clone())
+                .collect::<Vec<BaseRunner>>();
+            let inherited = |id: &str| BaseRunner {
+                inherited_by: Some("myerr001".to_string()),
+                ..runner(id, "rijoj001")
+            };
+            assert_eq!(scored, vec![inherited("ramir001"), inherited("biggc001")]);
+
+            let play = &plays[4];
+            assert_eq!(play.get_outs(), 0);
+            assert_eq!(play.get_runner(&Base::Second), Some(&runner("davig001", "myerr001")));
+            assert!(play.get_runner_advances()[0].advance.out);
+        }
+
+        #[test]
+        fn track_inherited_runners() {
+            let mut parser = GameParser::new();
+
+            let game = "id,CIN199004090
+version,2
+info,visteam,HOU
+info,hometeam,CIN
+start,youngg001,\"Gerald Young\",0,1,8
+start,biggc001,\"Craig Biggio\",0,2,2
+start,davig001,\"Glenn Davis\",0,3,3
+start,doraw001,\"Bill Doran\",0,4,4
+start,rijoj001,\"Jose Rijo\",1,0,1
+play,1,0,youngg001,00,X,S7
+play,1,0,biggc001,00,X,S8.1-2
+sub,myerr001,\"Randy Myers\",1,0,1
+play,1,0,davig001,00,X,FC5.2X3(5);1-2
+play,1,0,doraw001,00,X,D9.2-H;1-H
+play,2,1,larkb001,00,X,HR";
+            let game = parser.parse_game(game).unwrap();
+
+            // davis replaced young on a fielder's choice, so his run is still the starter's
+            let play = &game.get_plays()[3];
+            assert_eq!(play.get_runner(&Base::First).unwrap().pitcher_id, Some("rijoj001".to_string()));
+            assert_eq!(play.get_runner(&Base::Second).unwrap().pitcher_id, Some("rijoj001".to_string()));
+
+            assert_eq!(game.get_pitcher_responsibilities(), vec![
+                PitcherResponsibility {
+                    pitcher_id: "rijoj001".to_string(),
+                    runs_allowed: 2,
+                    inherited_runners: 0,
+                    inherited_runners_scored: 0,
+                },
+                PitcherResponsibility {
+                    pitcher_id: "myerr001".to_string(),
+                    runs_allowed: 0,
+                    inherited_runners: 2,
+                    inherited_runners_scored: 2,
+                },
+            ]);
+        }
+
+        #[test]
+        fn place_runners_from_adjustments() {
+            let mut parser = GameParser::new();
+
+            let game = "id,CIN202007240
+version,2
+info,visteam,HOU
+info,hometeam,CIN
+start,youngg001,\"Gerald Young\",0,1,8
+start,biggc001,\"Craig Biggio\",0,2,2
+start,rijoj001,\"Jose Rijo\",1,0,1
+play,9,0,youngg001,00,X,8/F
+radj,youngg001,2
+sub,myerr001,\"Randy Myers\",1,0,1
+badj,biggc001,L
+play,10,0,biggc001,00,X,S8/G.2-H";
+            let game = parser.parse_game(game).unwrap();
+
+            let play = &game.get_plays()[1];
+            assert_eq!(play.get_runner(&Base::Second), Some(&BaseRunner {
+                id: "youngg001".to_string(),
+                pitcher_id: Some("myerr001".to_string()),
+                inherited_by: None,
+            }));
+            assert_eq!(play.get_runs(), 1);
+            assert_eq!(game.get_adjustments()[1], AdjustmentEntry {
+                adjustment: Adjustment::BattingHand { player_id: "biggc001".to_string(), hand: Hand::Left },
+                play_index: 1,
+            });
+        }
     }
 }
@@ -1,9 +1,77 @@
-use std::collections::HashSet;
-use strum_macros::{EnumString, VariantNames};
+//! The data model of a parsed event file.
+//!
+//! With the `serde` feature, codes are serialized as they're written in event files, such as
+//! `"1"` for the home team, `"E6"` for a modifier or `"32"` for a count. Everything else is
+//! serialized field by field, with enums externally tagged.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString, VariantNames};
+
+use super::{Info, Line};
+
+/// Serializes each type through its `Display` and `FromStr` codes.
+#[cfg(feature = "serde")]
+macro_rules! serialize_as_code {
+    ($($code:ty),* $(,)?) => {$(
+        impl serde::Serialize for $code {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $code {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let code = <String as serde::Deserialize>::deserialize(deserializer)?;
+                code.parse().map_err(|_| serde::de::Error::custom(format!("invalid code: {code}")))
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "serde")]
+serialize_as_code!(
+    Team,
+    PitchesInfo,
+    DayNightInfo,
+    FieldConditionInfo,
+    PrecipitationInfo,
+    SkyInfo,
+    WindDirectionInfo,
+    GameTypeInfo,
+    Hand,
+    Position,
+    Count,
+    PitchType,
+    PitchModifier,
+    Fielder,
+    Base,
+    FieldLocation,
+    Trajectory,
+    Runner,
+    EventModifier,
+    PlayNote,
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum Team {
+    #[strum(serialize = "0")]
+    Visiting,
+    #[strum(serialize = "1")]
+    Home,
+}
 
-use super::{Info, Team};
+impl Team {
+    pub fn opponent(&self) -> Self {
+        match self {
+            Team::Visiting => Team::Home,
+            Team::Home => Team::Visiting,
+        }
+    }
+}
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 #[strum(serialize_all = "lowercase")]
 pub enum PitchesInfo {
     Pitches,
@@ -11,23 +79,24 @@ pub enum PitchesInfo {
     None,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 #[strum(serialize_all = "lowercase")]
 pub enum DayNightInfo {
     Day,
     Night,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 #[strum(serialize_all = "lowercase")]
 pub enum FieldConditionInfo {
+    Damp,
     Dry,
     Soaked,
     Wet,
     Unknown,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 #[strum(serialize_all = "lowercase")]
 pub enum PrecipitationInfo {
     Drizzle,
@@ -38,7 +107,7 @@ pub enum PrecipitationInfo {
     Unknown,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 #[strum(serialize_all = "lowercase")]
 pub enum SkyInfo {
     Cloudy,
@@ -50,12 +119,13 @@ pub enum SkyInfo {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TemperatureInfo {
     Known(u8),
     Unknown,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 pub enum WindDirectionInfo {
     #[strum(serialize = "fromcf")]
     FromCenterField,
@@ -78,24 +148,27 @@ pub enum WindDirectionInfo {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WindSpeedInfo {
     Known(u8),
     Unknown,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeOfGameInfo {
     Known(u16),
     Unknown,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttendanceInfo {
     Known(u32),
     Unknown,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, VariantNames)]
 #[strum(serialize_all = "lowercase")]
 pub enum GameTypeInfo {
     Regular,
@@ -112,6 +185,7 @@ pub enum GameTypeInfo {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InfoState {
     pub visiting_team: Option<String>,
     pub home_team: Option<String>,
@@ -186,14 +260,28 @@ impl Default for InfoState {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, EnumString)]
 pub enum Hand {
+    #[strum(serialize = "L")]
     Left,
+    #[strum(serialize = "R")]
     Right,
+    #[strum(serialize = "B")]
     Both,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumString, VariantNames)]
+impl Hand {
+    /// The side a switch hitter bats from against a pitcher throwing with this hand.
+    pub fn opposite(&self) -> Self {
+        match self {
+            Hand::Left => Hand::Right,
+            Hand::Right => Hand::Left,
+            Hand::Both => Hand::Both,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display, EnumString, VariantNames)]
 pub enum Position {
     #[strum(serialize = "1")]
     Pitcher,
@@ -221,7 +309,22 @@ pub enum Position {
     PinchRunner,
 }
 
+impl Position {
+    pub fn from_fielder(fielder: &Fielder) -> Option<Self> {
+        if let Fielder::Known(fielder) = fielder {
+            return fielder.to_string().parse().ok();
+        }
+
+        None
+    }
+
+    pub fn is_fielding(&self) -> bool {
+        !matches!(self, Position::PinchHitter | Position::PinchRunner)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
     pub id: String,
     pub name: String,
@@ -232,6 +335,51 @@ pub struct Player {
     pub batting_order: u8,
 }
 
+/// The players in the field for one team, keyed by position.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alignment {
+    fielders: HashMap<Position, String>,
+}
+
+impl Alignment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&String> {
+        self.fielders.get(position)
+    }
+
+    pub fn get_fielder(&self, fielder: &Fielder) -> Option<&String> {
+        Position::from_fielder(fielder).and_then(|position| self.get(&position))
+    }
+
+    /// Every filled position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Position, &String)> {
+        self.fielders.iter()
+    }
+
+    pub fn position_of(&self, player_id: &str) -> Option<Position> {
+        self.fielders
+            .iter()
+            .find(|(_, id)| id.as_str() == player_id)
+            .map(|(position, _)| *position)
+    }
+
+    /// Puts a player at a position. A player can only be in one place at a time, so any
+    /// position they previously held is vacated. Pinch hitters and pinch runners don't
+    /// take the field and are ignored.
+    pub fn set(&mut self, position: Position, player_id: String) {
+        if !position.is_fielding() {
+            return;
+        }
+
+        self.fielders.retain(|_, id| *id != player_id);
+        self.fielders.insert(position, player_id);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Count {
     Known {
@@ -241,7 +389,60 @@ pub enum Count {
     Unknown,
 }
 
-#[derive(Clone, Debug, PartialEq, EnumString, VariantNames)]
+impl Count {
+    /// The count after a pitch. Fouls don't add a third strike, though foul bunts and foul
+    /// tips do, and anything that isn't a pitch leaves the count alone.
+    pub fn after(&self, pitch_type: &PitchType) -> Self {
+        match self {
+            Count::Known { balls, strikes } => {
+                let (balls, strikes) = if pitch_type.is_ball() {
+                    (balls + 1, *strikes)
+                } else if pitch_type.is_foul() {
+                    (*balls, if *strikes < 2 { strikes + 1 } else { *strikes })
+                } else if pitch_type.is_strike() {
+                    (*balls, strikes + 1)
+                } else {
+                    (*balls, *strikes)
+                };
+
+                Count::Known { balls, strikes }
+            },
+            Count::Unknown => Count::Unknown,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Count::Known { balls, strikes } if *balls >= 4 || *strikes >= 3)
+    }
+}
+
+impl FromStr for Count {
+    type Err = String;
+
+    fn from_str(count: &str) -> Result<Self, Self::Err> {
+        let digits = count.chars().map(|c| c.to_digit(10)).collect::<Vec<Option<u32>>>();
+        match digits.as_slice() {
+            _ if count == "??" => Ok(Count::Unknown),
+            [Some(balls @ 0..=3), Some(strikes @ 0..=2)] => Ok(Count::Known {
+                balls: *balls as u8,
+                strikes: *strikes as u8,
+            }),
+            _ => Err(format!("Invalid count: {count}")),
+        }
+    }
+}
+
+/// Written as in a `play` record, with `??` for an unknown count.
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Count::Known { balls, strikes } => write!(f, "{balls}{strikes}"),
+            Count::Unknown => write!(f, "??"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Display, EnumString, VariantNames)]
 pub enum PitchType {
     #[strum(serialize = "A")]
     AutomaticStrike,
@@ -293,7 +494,56 @@ pub enum PitchType {
     NotInvolvingBatter,
 }
 
-#[derive(Clone, Debug, PartialEq, EnumString, VariantNames)]
+impl PitchType {
+    /// Whether this is a pitch to the batter, rather than a pickoff throw, a no-pitch or the
+    /// marker for a play not involving the batter.
+    pub fn is_pitch(&self) -> bool {
+        !matches!(
+            self,
+            PitchType::NoPitch
+                | PitchType::PickoffThrowToFirstBase
+                | PitchType::PickoffThrowToSecondBase
+                | PitchType::PickoffThrowToThirdBase
+                | PitchType::NotInvolvingBatter,
+        )
+    }
+
+    pub fn is_ball(&self) -> bool {
+        matches!(
+            self,
+            PitchType::Ball | PitchType::IntentionalBall | PitchType::Pitchout | PitchType::CalledBall,
+        )
+    }
+
+    /// Whether the pitch adds a strike, including fouls.
+    pub fn is_strike(&self) -> bool {
+        matches!(
+            self,
+            PitchType::AutomaticStrike
+                | PitchType::CalledStrike
+                | PitchType::Foul
+                | PitchType::Strike
+                | PitchType::FoulBunt
+                | PitchType::MissedBuntAttempt
+                | PitchType::FoulTipOnBunt
+                | PitchType::SwingingOnPitchout
+                | PitchType::FoulBallOnPitchout
+                | PitchType::SwingingStrike
+                | PitchType::FoulTip,
+        )
+    }
+
+    /// Whether the pitch is a foul that can't be a third strike.
+    pub fn is_foul(&self) -> bool {
+        matches!(self, PitchType::Foul | PitchType::FoulBallOnPitchout)
+    }
+
+    pub fn is_in_play(&self) -> bool {
+        matches!(self, PitchType::BallInPlayByBatter | PitchType::BallInPlayOnPitchout)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Display, EnumString, VariantNames)]
 pub enum PitchModifier {
     #[strum(serialize = "+")]
     PickoffThrowByCatcher,
@@ -305,12 +555,24 @@ pub enum PitchModifier {
 
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pitch {
     pub pitch_type: PitchType,
     pub pitch_modifier: Option<PitchModifier>,
 }
 
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(pitch_modifier) = &self.pitch_modifier {
+            write!(f, "{pitch_modifier}")?;
+        }
+
+        write!(f, "{}", self.pitch_type)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FieldingErrorType {
     ThrowingError(Option<Base>),
     NonThrowingError,
@@ -322,7 +584,32 @@ pub enum Fielder {
     Unknown,
 }
 
+impl FromStr for Fielder {
+    type Err = String;
+
+    fn from_str(fielder: &str) -> Result<Self, Self::Err> {
+        match fielder {
+            "U" => Ok(Fielder::Unknown),
+            _ if fielder.len() == 1 => match fielder.parse::<u8>() {
+                Ok(fielder @ 1..=9) => Ok(Fielder::Known(fielder)),
+                _ => Err(format!("Invalid fielder: {fielder}")),
+            },
+            _ => Err(format!("Invalid fielder: {fielder}")),
+        }
+    }
+}
+
+impl fmt::Display for Fielder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fielder::Known(fielder) => write!(f, "{fielder}"),
+            Fielder::Unknown => write!(f, "U"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BallPathNode {
     Success {
         fielder: Fielder,
@@ -333,7 +620,41 @@ pub enum BallPathNode {
     },
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, EnumString, VariantNames)]
+impl BallPathNode {
+    pub fn get_fielder(&self) -> &Fielder {
+        match self {
+            BallPathNode::Success { fielder } => fielder,
+            BallPathNode::Error { fielder, .. } => fielder,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, BallPathNode::Error { .. })
+    }
+}
+
+impl fmt::Display for BallPathNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BallPathNode::Success { fielder } => write!(f, "{fielder}"),
+            BallPathNode::Error { fielder, error_type: FieldingErrorType::NonThrowingError } => write!(f, "E{fielder}"),
+            BallPathNode::Error { fielder, error_type: FieldingErrorType::ThrowingError(base) } => {
+                write!(f, "E{fielder}/TH")?;
+                match base {
+                    Some(base) => write!(f, "{base}"),
+                    None => Ok(()),
+                }
+            },
+        }
+    }
+}
+
+/// Writes a ball path without its surrounding parentheses.
+fn write_ball_path(f: &mut fmt::Formatter<'_>, ball_path: &[BallPathNode]) -> fmt::Result {
+    ball_path.iter().try_for_each(|node| write!(f, "{node}"))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumString, VariantNames)]
 pub enum Base {
     #[strum(serialize = "1")]
     First,
@@ -345,8 +666,59 @@ pub enum Base {
     Home,
 }
 
+impl Base {
+    /// The index of an occupied base, from 0 for first to 2 for third. Home has none.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Base::First => Some(0),
+            Base::Second => Some(1),
+            Base::Third => Some(2),
+            Base::Home => None,
+        }
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Base::First),
+            1 => Some(Base::Second),
+            2 => Some(Base::Third),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Base::First => Base::Second,
+            Base::Second => Base::Third,
+            Base::Third => Base::Home,
+            Base::Home => Base::First,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            Base::First => Base::Home,
+            Base::Second => Base::First,
+            Base::Third => Base::Second,
+            Base::Home => Base::Third,
+        }
+    }
+}
+
+/// Home is written as `H`. An advance starting from home is written `B` by `Advance`.
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base::First => write!(f, "1"),
+            Base::Second => write!(f, "2"),
+            Base::Third => write!(f, "3"),
+            Base::Home => write!(f, "H"),
+        }
+    }
+}
+
 // taken from https://www.retrosheet.org/location.htm
-#[derive(Clone, Debug, PartialEq, EnumString, VariantNames)]
+#[derive(Clone, Debug, PartialEq, Display, EnumString, VariantNames)]
 pub enum FieldLocation {
     // outfield
     #[strum(serialize = "78XD")]
@@ -432,6 +804,8 @@ pub enum FieldLocation {
     DeepFirstBaseFence,
     #[strum(serialize = "5F")]
     ThirdBaseFence,
+    #[strum(serialize = "5")]
+    ThirdBase,
     #[strum(serialize = "56")]
     ThirdShortstop,
     #[strum(serialize = "6")]
@@ -486,7 +860,70 @@ pub enum FieldLocation {
     CatcherFence,
 }
 
-#[derive(Clone, Debug, PartialEq, EnumString, VariantNames)]
+/// The part of the field a ball went to, as seen from home plate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldSide {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldDepth {
+    Short,
+    Normal,
+    Deep,
+    ExtremelyDeep,
+}
+
+impl FieldLocation {
+    pub fn is_outfield(&self) -> bool {
+        self.zone().2
+    }
+
+    /// Left takes in third base and shortstop, and right first and second base, while the
+    /// middle of the diamond, the pitcher and the catcher are center.
+    pub fn get_side(&self) -> FieldSide {
+        self.zone().0
+    }
+
+    pub fn get_depth(&self) -> FieldDepth {
+        self.zone().1
+    }
+
+    /// The side and depth of the location, and whether it's in the outfield.
+    fn zone(&self) -> (FieldSide, FieldDepth, bool) {
+        match self {
+            FieldLocation::ExtremelyDeepCenterLeft => (FieldSide::Left, FieldDepth::ExtremelyDeep, true),
+            FieldLocation::ExtremelyDeepCenter => (FieldSide::Center, FieldDepth::ExtremelyDeep, true),
+            FieldLocation::ExtremelyDeepCenterRight => (FieldSide::Right, FieldDepth::ExtremelyDeep, true),
+            FieldLocation::DeepLeftFoulFence | FieldLocation::DeepLeftFoul | FieldLocation::DeepLeft | FieldLocation::DeepCenterLeft => (FieldSide::Left, FieldDepth::Deep, true),
+            FieldLocation::DeepCenter => (FieldSide::Center, FieldDepth::Deep, true),
+            FieldLocation::DeepCenterRight | FieldLocation::DeepRight | FieldLocation::DeepRightFoul | FieldLocation::DeepRightFoulFence => (FieldSide::Right, FieldDepth::Deep, true),
+            FieldLocation::LeftFoulFence | FieldLocation::LeftFoul | FieldLocation::Left | FieldLocation::CenterLeft => (FieldSide::Left, FieldDepth::Normal, true),
+            FieldLocation::Center => (FieldSide::Center, FieldDepth::Normal, true),
+            FieldLocation::CenterRight | FieldLocation::Right | FieldLocation::RightFoul | FieldLocation::RightFoulFence => (FieldSide::Right, FieldDepth::Normal, true),
+            FieldLocation::ShortLeftFoulFence | FieldLocation::ShortLeftFoul | FieldLocation::ShortLeft | FieldLocation::ShortCenterLeft => (FieldSide::Left, FieldDepth::Short, true),
+            FieldLocation::ShortCenter => (FieldSide::Center, FieldDepth::Short, true),
+            FieldLocation::ShortCenterRight | FieldLocation::ShortRight | FieldLocation::ShortRightFoul | FieldLocation::ShortRightFoulFence => (FieldSide::Right, FieldDepth::Short, true),
+            FieldLocation::DeepThirdBaseFence | FieldLocation::DeepThirdBase | FieldLocation::DeepThirdShortstop | FieldLocation::DeepShortstop => (FieldSide::Left, FieldDepth::Deep, false),
+            FieldLocation::DeepMiddleShortstop | FieldLocation::DeepMiddleSecondBase => (FieldSide::Center, FieldDepth::Deep, false),
+            FieldLocation::DeepSecondBase | FieldLocation::DeepSecondFirstBase | FieldLocation::DeepFirstBase | FieldLocation::DeepFirstBaseFence => (FieldSide::Right, FieldDepth::Deep, false),
+            FieldLocation::ThirdBaseFence | FieldLocation::ThirdBase | FieldLocation::ThirdShortstop | FieldLocation::Shortstop => (FieldSide::Left, FieldDepth::Normal, false),
+            FieldLocation::MiddleShortstop | FieldLocation::MiddleSecondBase => (FieldSide::Center, FieldDepth::Normal, false),
+            FieldLocation::SecondBase | FieldLocation::SecondFirstBase | FieldLocation::FirstBase | FieldLocation::FirstBaseFence => (FieldSide::Right, FieldDepth::Normal, false),
+            FieldLocation::ShortThirdBase | FieldLocation::ShortThirdShortstop | FieldLocation::ShortShortstop => (FieldSide::Left, FieldDepth::Short, false),
+            FieldLocation::ShortMiddleShortstop | FieldLocation::ShortMiddleSecondBase => (FieldSide::Center, FieldDepth::Short, false),
+            FieldLocation::ShortSecondBase | FieldLocation::ShortSecondFirstBase | FieldLocation::ShortFirstBase => (FieldSide::Right, FieldDepth::Short, false),
+            FieldLocation::PitcherThird | FieldLocation::CatcherThirdFence | FieldLocation::CatcherThird => (FieldSide::Left, FieldDepth::Normal, false),
+            FieldLocation::Pitcher | FieldLocation::Catcher | FieldLocation::CatcherFence => (FieldSide::Center, FieldDepth::Normal, false),
+            FieldLocation::ShortPitcher => (FieldSide::Center, FieldDepth::Short, false),
+            FieldLocation::PitcherFirst | FieldLocation::CatcherFirst | FieldLocation::CatcherFirstFence => (FieldSide::Right, FieldDepth::Normal, false),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Display, EnumString, VariantNames)]
 pub enum Runner {
     #[strum(serialize = "B")]
     Batter,
@@ -499,21 +936,19 @@ pub enum Runner {
 }
 
 impl Runner {
-    pub fn from_fielder(fielder: &Fielder) -> Option<Self> {
-        if let Fielder::Known(fielder) = fielder {
-            return match fielder {
-                3 => Some(Runner::Third),
-                4 => Some(Runner::Second),
-                5 => Some(Runner::First),
-                _ => None,
-            };
+    /// The base the runner started the play on, where home stands for the batter.
+    pub fn base(&self) -> Base {
+        match self {
+            Runner::Batter => Base::Home,
+            Runner::First => Base::First,
+            Runner::Second => Base::Second,
+            Runner::Third => Base::Third,
         }
-
-        None
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventType {
     Out {
         credited_fielder: Fielder,
@@ -587,14 +1022,262 @@ pub enum EventType {
     },
 }
 
+impl EventType {
+    /// The advances the event type implies without them being written out, such as the
+    /// batter reaching second on a double or a runner being put out on a caught stealing.
+    pub fn implied_advances(&self) -> Vec<Advance> {
+        match self {
+            EventType::Out { runner_out: out, .. } => {
+                let mut advances = vec![Advance::runner_out(out)];
+                if *out != Runner::Batter {
+                    advances.push(Advance::new(Base::Home, Base::First, false));
+                }
+
+                advances
+            },
+            EventType::DoublePlay { runners_out, .. } | EventType::TriplePlay { runners_out, .. } => {
+                let mut advances = runners_out.iter().map(Advance::runner_out).collect::<Vec<Advance>>();
+                if !runners_out.contains(&Runner::Batter) {
+                    advances.push(Advance::new(Base::Home, Base::First, false));
+                }
+
+                advances
+            },
+            EventType::Single { .. }
+            | EventType::Interference
+            | EventType::Error { .. }
+            | EventType::FieldersChoice { .. }
+            | EventType::HitByPitch => vec![Advance::new(Base::Home, Base::First, false)],
+            EventType::Double { .. } | EventType::GroundRuleDouble => vec![Advance::new(Base::Home, Base::Second, false)],
+            EventType::Triple { .. } => vec![Advance::new(Base::Home, Base::Third, false)],
+            EventType::SoloHomeRun | EventType::InsideTheParkHomeRun { .. } => vec![Advance::new(Base::Home, Base::Home, false)],
+            EventType::Strikeout { base_running_event, .. } => {
+                let mut advances = vec![Advance::new(Base::Home, Base::First, true)];
+                if let Some(base_running_event) = base_running_event.as_ref() {
+                    advances.extend(base_running_event.implied_advances());
+                }
+
+                advances
+            },
+            EventType::Walk { base_running_event, .. } => {
+                let mut advances = vec![Advance::new(Base::Home, Base::First, false)];
+                if let Some(base_running_event) = base_running_event.as_ref() {
+                    advances.extend(base_running_event.implied_advances());
+                }
+
+                advances
+            },
+            EventType::CaughtStealing { base, ball_path } => {
+                let out = !ball_path.iter().any(BallPathNode::is_error);
+                vec![Advance::new(base.previous(), *base, out)]
+            },
+            EventType::Pickoff { caught_stealing: true, base, ball_path } => {
+                let out = !ball_path.iter().any(BallPathNode::is_error);
+                vec![Advance::new(base.previous(), *base, out)]
+            },
+            EventType::Pickoff { caught_stealing: false, base, ball_path } => {
+                if ball_path.iter().any(BallPathNode::is_error) {
+                    Vec::new()
+                } else {
+                    vec![Advance::new(*base, *base, true)]
+                }
+            },
+            EventType::StolenBase { bases } => bases
+                .iter()
+                .map(|base| Advance::new(base.previous(), *base, false))
+                .collect(),
+            EventType::ErrorOnFoulFlyBall { .. }
+            | EventType::NoPlay
+            | EventType::Balk
+            | EventType::DefensiveIndifference
+            | EventType::OtherAdvance
+            | EventType::PassedBall
+            | EventType::WildPitch => Vec::new(),
+        }
+    }
+
+    /// Whether the event ends the batter's plate appearance. Base running events and a
+    /// muffed foul fly leave the batter at the plate.
+    pub fn ends_plate_appearance(&self) -> bool {
+        match self {
+            EventType::Out { .. }
+            | EventType::DoublePlay { .. }
+            | EventType::TriplePlay { .. }
+            | EventType::Interference
+            | EventType::Single { .. }
+            | EventType::Double { .. }
+            | EventType::GroundRuleDouble
+            | EventType::Triple { .. }
+            | EventType::Error { .. }
+            | EventType::FieldersChoice { .. }
+            | EventType::SoloHomeRun
+            | EventType::InsideTheParkHomeRun { .. }
+            | EventType::HitByPitch
+            | EventType::Strikeout { .. }
+            | EventType::Walk { .. } => true,
+            EventType::ErrorOnFoulFlyBall { .. }
+            | EventType::NoPlay
+            | EventType::Balk
+            | EventType::CaughtStealing { .. }
+            | EventType::DefensiveIndifference
+            | EventType::OtherAdvance
+            | EventType::PassedBall
+            | EventType::WildPitch
+            | EventType::Pickoff { .. }
+            | EventType::StolenBase { .. } => false,
+        }
+    }
+
+    /// Whether the event counts as a plate appearance for the batter.
+    pub fn is_plate_appearance(&self) -> bool {
+        self.ends_plate_appearance()
+    }
+
+    pub fn is_hit(&self) -> bool {
+        self.total_bases() > 0
+    }
+
+    /// Whether the batter is awarded first base, forcing any runners behind them.
+    pub fn forces_runners(&self) -> bool {
+        matches!(self, EventType::Walk { .. } | EventType::HitByPitch | EventType::Interference)
+    }
+
+    /// The event type along with any base running event that happened on the same play.
+    pub fn with_base_running_event(&self) -> Vec<&EventType> {
+        match self {
+            EventType::Strikeout { base_running_event, .. } | EventType::Walk { base_running_event, .. } => {
+                let mut event_types = vec![self];
+                event_types.extend(base_running_event.as_ref().as_ref());
+                event_types
+            },
+            _ => vec![self],
+        }
+    }
+
+    pub fn total_bases(&self) -> u8 {
+        match self {
+            EventType::Single { .. } => 1,
+            EventType::Double { .. } | EventType::GroundRuleDouble => 2,
+            EventType::Triple { .. } => 3,
+            EventType::SoloHomeRun | EventType::InsideTheParkHomeRun { .. } => 4,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fielders = |f: &mut fmt::Formatter<'_>, fielders: &[Fielder]| fielders.iter().try_for_each(|fielder| write!(f, "{fielder}"));
+
+        match self {
+            EventType::Out { credited_fielder, assisting_fielders, runner_out } => {
+                fielders(f, assisting_fielders)?;
+                write!(f, "{credited_fielder}")?;
+                if *runner_out != Runner::Batter {
+                    write!(f, "({runner_out})")?;
+                }
+
+                Ok(())
+            },
+            EventType::DoublePlay { credited_fielders, assisting_fielders, runners_out }
+            | EventType::TriplePlay { credited_fielders, assisting_fielders, runners_out } => {
+                // the segments aren't kept, so an assist goes in the first one whose putout
+                // it didn't make: 64(1)3, but 8(B)84(2)
+                let mut segments = vec![Vec::new(); credited_fielders.len()];
+                let mut segment = 0;
+                for assist in assisting_fielders {
+                    if credited_fielders.get(segment) == Some(assist) && segment + 1 < credited_fielders.len() {
+                        segment += 1;
+                    }
+
+                    segments[segment].push(*assist);
+                }
+
+                for (index, (credited_fielder, assists)) in credited_fielders.iter().zip(&segments).enumerate() {
+                    fielders(f, assists)?;
+                    write!(f, "{credited_fielder}")?;
+
+                    let last = index + 1 == credited_fielders.len();
+                    match runners_out.get(index) {
+                        Some(Runner::Batter) if last => {},
+                        Some(runner) => write!(f, "({runner})")?,
+                        None => {},
+                    }
+                }
+
+                Ok(())
+            },
+            EventType::Interference => write!(f, "C"),
+            EventType::Single { credited_fielder, assisting_fielders }
+            | EventType::Double { credited_fielder, assisting_fielders }
+            | EventType::Triple { credited_fielder, assisting_fielders } => {
+                let code = match self {
+                    EventType::Single { .. } => "S",
+                    EventType::Double { .. } => "D",
+                    _ => "T",
+                };
+
+                write!(f, "{code}")?;
+                fielders(f, assisting_fielders)?;
+                match credited_fielder {
+                    Fielder::Unknown if assisting_fielders.is_empty() => Ok(()),
+                    _ => write!(f, "{credited_fielder}"),
+                }
+            },
+            EventType::GroundRuleDouble => write!(f, "DGR"),
+            EventType::Error { credited_fielder, assisting_fielders } => {
+                fielders(f, assisting_fielders)?;
+                write!(f, "E{credited_fielder}")
+            },
+            EventType::FieldersChoice { credited_fielder } => write!(f, "FC{credited_fielder}"),
+            EventType::ErrorOnFoulFlyBall { credited_fielder } => write!(f, "FLE{credited_fielder}"),
+            EventType::SoloHomeRun => write!(f, "HR"),
+            EventType::InsideTheParkHomeRun { credited_fielder } => write!(f, "HR{credited_fielder}"),
+            EventType::HitByPitch => write!(f, "HP"),
+            EventType::Strikeout { ball_path, base_running_event } => {
+                write!(f, "K")?;
+                write_ball_path(f, ball_path)?;
+                match base_running_event.as_ref() {
+                    Some(event_type) => write!(f, "+{event_type}"),
+                    None => Ok(()),
+                }
+            },
+            EventType::NoPlay => write!(f, "NP"),
+            EventType::Walk { intentional, base_running_event } => {
+                write!(f, "{}", if *intentional { "IW" } else { "W" })?;
+                match base_running_event.as_ref() {
+                    Some(event_type) => write!(f, "+{event_type}"),
+                    None => Ok(()),
+                }
+            },
+            EventType::Balk => write!(f, "BK"),
+            EventType::CaughtStealing { base, ball_path } => {
+                write!(f, "CS{base}(")?;
+                write_ball_path(f, ball_path)?;
+                write!(f, ")")
+            },
+            EventType::DefensiveIndifference => write!(f, "DI"),
+            EventType::OtherAdvance => write!(f, "OA"),
+            EventType::PassedBall => write!(f, "PB"),
+            EventType::WildPitch => write!(f, "WP"),
+            EventType::Pickoff { caught_stealing, base, ball_path } => {
+                write!(f, "{}{base}(", if *caught_stealing { "POCS" } else { "PO" })?;
+                write_ball_path(f, ball_path)?;
+                write!(f, ")")
+            },
+            EventType::StolenBase { bases } => {
+                let bases = bases.iter().map(|base| format!("SB{base}")).collect::<Vec<String>>();
+                write!(f, "{}", bases.join(";"))
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum EventModifier {
     AppealPlay,
-    PopUpBunt,
-    GroundBallBunt,
     BuntGroundedIntoDoublePlay,
     BatterInterference,
-    LineDriveBunt,
     BattingOutOfTurn,
     BuntPoppedIntoDoublePlay,
     RunnerHitByBattedBall,
@@ -604,24 +1287,20 @@ pub enum EventModifier {
     CourtesyRunner,
     UnspecifiedDoublePlay,
     Error(Fielder),
-    Fly,
     FlyBallDoublePlay,
     FanInterference,
     Foul,
     ForceOut,
-    GroundBall,
     GroundBallDoublePlay,
     GroundBallTriplePlay,
     InfieldFlyRule,
     Interference,
     InsideTheParkHomeRun,
-    LineDrive,
     LinedIntoDoublePlay,
     LinedIntoTriplePlay,
     ManagerChallengeOfCallOnTheField,
     NoDoublePlayCreditedForThisPlay,
     FielderObstructingRunner,
-    PopFly,
     RunnerPassedAnotherRunnerAndWasCalledOut,
     RelayThrowFromFielderWithNoOut(Fielder),
     RunnerInterference,
@@ -631,10 +1310,121 @@ pub enum EventModifier {
     UnspecifiedTriplePlay,
     UmpireInterference,
     UmpireReviewOfCallOnTheField,
-    HitLocation(FieldLocation),
+    BattedBall(BattedBall),
+}
+
+impl EventModifier {
+    fn canonical_rank(&self) -> u8 {
+        match self {
+            EventModifier::Throw(_) | EventModifier::RelayThrowFromFielderWithNoOut(_) | EventModifier::Error(_) => 1,
+            EventModifier::BattedBall(_) => 2,
+            _ => 0,
+        }
+    }
+}
+
+/// How a ball was hit, as written before its location in a modifier such as `/G56`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum Trajectory {
+    #[strum(serialize = "G")]
+    GroundBall,
+    #[strum(serialize = "L")]
+    LineDrive,
+    #[strum(serialize = "F")]
+    Fly,
+    #[strum(serialize = "P")]
+    PopFly,
+    #[strum(serialize = "BG")]
+    GroundBallBunt,
+    #[strum(serialize = "BL")]
+    LineDriveBunt,
+    #[strum(serialize = "BP")]
+    PopUpBunt,
+}
+
+impl Trajectory {
+    pub fn is_bunt(&self) -> bool {
+        matches!(self, Trajectory::GroundBallBunt | Trajectory::LineDriveBunt | Trajectory::PopUpBunt)
+    }
+}
+
+/// The trajectory and location of a ball in play, either of which may be missing, as in
+/// `/G` or `/78`. Displayed as it's written, such as `F78XD`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BattedBall {
+    pub trajectory: Option<Trajectory>,
+    pub location: Option<FieldLocation>,
+}
+
+impl fmt::Display for BattedBall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(trajectory) = &self.trajectory {
+            write!(f, "{trajectory}")?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, "{location}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EventModifier {
+    type Err = String;
+
+    fn from_str(modifier: &str) -> Result<Self, Self::Err> {
+        Line::parse_event_modifier(modifier)
+    }
+}
+
+impl fmt::Display for EventModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            EventModifier::AppealPlay => "AP",
+            EventModifier::BuntGroundedIntoDoublePlay => "BGDP",
+            EventModifier::BatterInterference => "BINT",
+            EventModifier::BattingOutOfTurn => "BOOT",
+            EventModifier::BuntPoppedIntoDoublePlay => "BPDP",
+            EventModifier::RunnerHitByBattedBall => "BR",
+            EventModifier::CalledThirdStrike => "C",
+            EventModifier::CourtesyBatter => "COUB",
+            EventModifier::CourtesyFielder => "COUF",
+            EventModifier::CourtesyRunner => "COUR",
+            EventModifier::UnspecifiedDoublePlay => "DP",
+            EventModifier::Error(fielder) => return write!(f, "E{fielder}"),
+            EventModifier::FlyBallDoublePlay => "FDP",
+            EventModifier::FanInterference => "FINT",
+            EventModifier::Foul => "FL",
+            EventModifier::ForceOut => "FO",
+            EventModifier::GroundBallDoublePlay => "GDP",
+            EventModifier::GroundBallTriplePlay => "GTP",
+            EventModifier::InfieldFlyRule => "IF",
+            EventModifier::Interference => "INT",
+            EventModifier::InsideTheParkHomeRun => "IPHR",
+            EventModifier::LinedIntoDoublePlay => "LDP",
+            EventModifier::LinedIntoTriplePlay => "LTP",
+            EventModifier::ManagerChallengeOfCallOnTheField => "MREV",
+            EventModifier::NoDoublePlayCreditedForThisPlay => "NDP",
+            EventModifier::FielderObstructingRunner => "OBS",
+            EventModifier::RunnerPassedAnotherRunnerAndWasCalledOut => "PR",
+            EventModifier::RelayThrowFromFielderWithNoOut(fielder) => return write!(f, "R{fielder}"),
+            EventModifier::RunnerInterference => "RINT",
+            EventModifier::SacrificeFly => "SF",
+            EventModifier::SacrificeHitOrBunt => "SH",
+            EventModifier::Throw(Some(base)) => return write!(f, "TH{base}"),
+            EventModifier::Throw(None) => "TH",
+            EventModifier::UnspecifiedTriplePlay => "TP",
+            EventModifier::UmpireInterference => "UINT",
+            EventModifier::UmpireReviewOfCallOnTheField => "UREV",
+            EventModifier::BattedBall(batted_ball) => return write!(f, "{batted_ball}"),
+        };
+
+        write!(f, "{code}")
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AdvanceParameter {
     BallPath(Vec<BallPathNode>),
     Unearned,
@@ -646,22 +1436,358 @@ pub enum AdvanceParameter {
     PassedBall,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Advance {
-    pub starting_base: Base,
-    pub ending_base: Base,
-    pub out: bool,
+impl AdvanceParameter {
+    fn canonical_rank(&self) -> u8 {
+        match self {
+            AdvanceParameter::Unearned | AdvanceParameter::TeamUnearned => 1,
+            AdvanceParameter::RBICredited | AdvanceParameter::RBINotCredited => 2,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for AdvanceParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        match self {
+            AdvanceParameter::BallPath(ball_path) => write_ball_path(f, ball_path)?,
+            AdvanceParameter::Unearned => write!(f, "UR")?,
+            AdvanceParameter::TeamUnearned => write!(f, "TUR")?,
+            AdvanceParameter::RBICredited => write!(f, "RBI")?,
+            AdvanceParameter::RBINotCredited => write!(f, "NR")?,
+            AdvanceParameter::Interference(location) => write!(f, "{location}/INT")?,
+            AdvanceParameter::WildPitch => write!(f, "WP")?,
+            AdvanceParameter::PassedBall => write!(f, "PB")?,
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Advance {
+    pub starting_base: Base,
+    pub ending_base: Base,
+    pub out: bool,
     pub parameters: Vec<AdvanceParameter>,
 }
 
+impl Advance {
+    pub fn new(starting_base: Base, ending_base: Base, out: bool) -> Self {
+        Self {
+            starting_base,
+            ending_base,
+            out,
+            parameters: Vec::new(),
+        }
+    }
+
+    /// The runner put out trying for the next base.
+    pub fn runner_out(runner: &Runner) -> Self {
+        let base = runner.base();
+        Self::new(base, base.next(), true)
+    }
+
+    /// Whether the runner was actually put out. An advance written with `X` whose ball path
+    /// includes an error leaves the runner safe.
+    pub fn is_out(&self) -> bool {
+        self.out && !self.parameters.iter().any(|parameter| match parameter {
+            AdvanceParameter::BallPath(ball_path) => ball_path.iter().any(BallPathNode::is_error),
+            _ => false,
+        })
+    }
+
+    pub fn is_run(&self) -> bool {
+        self.ending_base == Base::Home && !self.is_out()
+    }
+}
+
+impl fmt::Display for Advance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.starting_base {
+            Base::Home => write!(f, "B")?,
+            base => write!(f, "{base}")?,
+        }
+
+        write!(f, "{}{}", if self.out { "X" } else { "-" }, self.ending_base)?;
+        self.parameters.iter().try_for_each(|parameter| write!(f, "{parameter}"))
+    }
+}
+
+/// A player on base, along with the pitcher charged with them. `inherited_by` is the
+/// reliever who most recently took over with the runner already on base.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaseRunner {
+    pub id: String,
+    pub pitcher_id: Option<String>,
+    pub inherited_by: Option<String>,
+}
+
+/// An advance tagged with the runner who made it. Starting from home means the batter.
+/// Unlike the advances written in the event, these include the implied ones, such as the
+/// batter reaching first on a single, and `out` is only set if the runner was retired.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunnerAdvance {
+    pub runner: BaseRunner,
+    pub advance: Advance,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event {
     pub event_type: EventType,
+    /// In the order written, so that an event writes back the way it was read.
     pub modifiers: Vec<EventModifier>,
     pub advances: Vec<Advance>,
 }
 
-#[derive(Clone, Debug, PartialEq, EnumString, VariantNames)]
+impl Event {
+    /// The event in the spelling the formatter writes: modifiers describing the play, then
+    /// throws and errors, then the batted ball, and advances from the lead runner back to the
+    /// batter. A trajectory and location written apart, as in `/G/4`, are joined into one.
+    pub fn to_canonical(&self) -> Event {
+        let mut modifiers = self.modifiers.clone();
+
+        let lone_trajectory = modifiers.iter().position(|modifier| matches!(
+            modifier,
+            EventModifier::BattedBall(BattedBall { trajectory: Some(_), location: None })
+        ));
+        let lone_location = modifiers.iter().position(|modifier| matches!(
+            modifier,
+            EventModifier::BattedBall(BattedBall { trajectory: None, location: Some(_) })
+        ));
+        if let (Some(trajectory_index), Some(location_index)) = (lone_trajectory, lone_location)
+            && let EventModifier::BattedBall(BattedBall { trajectory, .. }) = &modifiers[trajectory_index]
+            && let EventModifier::BattedBall(BattedBall { location, .. }) = &modifiers[location_index]
+        {
+            modifiers[trajectory_index] = EventModifier::BattedBall(BattedBall {
+                trajectory: *trajectory,
+                location: location.clone(),
+            });
+            modifiers.remove(location_index);
+        }
+
+        modifiers.sort_by_key(|modifier| (modifier.canonical_rank(), modifier.to_string()));
+
+        let mut advances = self.advances.clone();
+        for advance in &mut advances {
+            advance.parameters.sort_by_key(AdvanceParameter::canonical_rank);
+        }
+        advances.sort_by_key(|advance| std::cmp::Reverse(advance.starting_base.index().map(|index| index + 1).unwrap_or(0)));
+
+        Event {
+            event_type: self.event_type.clone(),
+            modifiers,
+            advances,
+        }
+    }
+
+    /// The ball in play, from the first batted ball modifier.
+    pub fn get_batted_ball(&self) -> Option<&BattedBall> {
+        self.modifiers.iter().find_map(|modifier| match modifier {
+            EventModifier::BattedBall(batted_ball) => Some(batted_ball),
+            _ => None,
+        })
+    }
+
+    /// The written advances along with the ones the event type implies, where a written
+    /// advance takes precedence. `out` is only set if the runner was actually retired.
+    pub fn get_all_advances(&self) -> Vec<Advance> {
+        let mut advances = Vec::<Advance>::new();
+        for advance in self.advances.iter().chain(self.event_type.implied_advances().iter()) {
+            if advances.iter().all(|a| a.starting_base != advance.starting_base) {
+                advances.push(Advance {
+                    out: advance.is_out(),
+                    ..advance.clone()
+                });
+            }
+        }
+
+        advances
+    }
+
+    pub fn is_sacrifice(&self) -> bool {
+        self.event_type.ends_plate_appearance()
+            && self.modifiers
+                .iter()
+                .any(|modifier| matches!(modifier, EventModifier::SacrificeFly | EventModifier::SacrificeHitOrBunt))
+    }
+
+    /// Whether the plate appearance counts as an at bat, which walks, hit batters,
+    /// interference and sacrifices don't.
+    pub fn is_at_bat(&self) -> bool {
+        self.event_type.is_plate_appearance() && !self.event_type.forces_runners() && !self.is_sacrifice()
+    }
+
+    pub fn outs_recorded(&self) -> u8 {
+        self.get_all_advances()
+            .iter()
+            .filter(|advance| advance.out)
+            .count() as u8
+    }
+
+    /// Whether the scorer marked the play as a double play, rather than just two outs being made
+    /// on it. `/NDP` takes the credit away.
+    pub fn is_double_play(&self) -> bool {
+        let marked = self.modifiers.iter().any(|modifier| matches!(
+            modifier,
+            EventModifier::BuntGroundedIntoDoublePlay
+                | EventModifier::BuntPoppedIntoDoublePlay
+                | EventModifier::UnspecifiedDoublePlay
+                | EventModifier::FlyBallDoublePlay
+                | EventModifier::GroundBallDoublePlay
+                | EventModifier::LinedIntoDoublePlay
+        ));
+
+        (marked || matches!(self.event_type, EventType::DoublePlay { .. }))
+            && !self.modifiers.contains(&EventModifier::NoDoublePlayCreditedForThisPlay)
+    }
+
+    /// Whether the scorer marked the play as a triple play.
+    pub fn is_triple_play(&self) -> bool {
+        let marked = self.modifiers.iter().any(|modifier| matches!(
+            modifier,
+            EventModifier::GroundBallTriplePlay | EventModifier::LinedIntoTriplePlay | EventModifier::UnspecifiedTriplePlay
+        ));
+
+        marked || matches!(self.event_type, EventType::TriplePlay { .. })
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.event_type)?;
+        for modifier in &self.modifiers {
+            write!(f, "/{modifier}")?;
+        }
+
+        if !self.advances.is_empty() {
+            let advances = self.advances.iter().map(Advance::to_string).collect::<Vec<String>>();
+            write!(f, ".{}", advances.join(";"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldingCreditType {
+    Putout,
+    Assist,
+    Error,
+}
+
+/// A putout, assist or error, along with the player at the fielder's position when the
+/// play happened.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldingCredit {
+    pub fielder: Fielder,
+    pub player_id: Option<String>,
+    pub credit_type: FieldingCreditType,
+}
+
+/// Credits along a ball path: the fielder who completes it gets the putout, and everyone
+/// before gets an assist. An error takes the place of the putout.
+fn ball_path_credits(ball_path: &[BallPathNode], out: bool) -> Vec<(Fielder, FieldingCreditType)> {
+    let mut credits = Vec::new();
+
+    let error = ball_path.iter().position(BallPathNode::is_error);
+    let last = error.unwrap_or(ball_path.len().saturating_sub(1));
+    for (index, node) in ball_path.iter().enumerate().take(last + 1) {
+        let credit_type = if node.is_error() {
+            FieldingCreditType::Error
+        } else if index < last {
+            FieldingCreditType::Assist
+        } else if out {
+            FieldingCreditType::Putout
+        } else {
+            continue;
+        };
+
+        credits.push((*node.get_fielder(), credit_type));
+    }
+
+    credits
+}
+
+/// Credits for an out made by a list of fielders, where the last one records the putout.
+fn out_credits(credited_fielder: &Fielder, assisting_fielders: &[Fielder]) -> Vec<(Fielder, FieldingCreditType)> {
+    let mut credits = assisting_fielders
+        .iter()
+        .map(|fielder| (*fielder, FieldingCreditType::Assist))
+        .collect::<Vec<(Fielder, FieldingCreditType)>>();
+
+    credits.push((*credited_fielder, FieldingCreditType::Putout));
+    credits
+}
+
+impl EventType {
+    /// The putouts, assists and errors the event type credits. `batter_out` says whether a
+    /// strikeout actually retired the batter.
+    fn fielding_credits(&self, batter_out: bool) -> Vec<(Fielder, FieldingCreditType)> {
+        match self {
+            EventType::Out { credited_fielder, assisting_fielders, .. } => out_credits(credited_fielder, assisting_fielders),
+            EventType::DoublePlay { credited_fielders, assisting_fielders, .. }
+            | EventType::TriplePlay { credited_fielders, assisting_fielders, .. } => {
+                let mut credits = assisting_fielders
+                    .iter()
+                    .map(|fielder| (*fielder, FieldingCreditType::Assist))
+                    .collect::<Vec<(Fielder, FieldingCreditType)>>();
+
+                // a fielder who makes a putout and then throws on for the next one, as the
+                // 4 in 64(1)3, is credited with an assist that isn't written out
+                for (index, fielder) in credited_fielders.iter().enumerate() {
+                    if let Some(next) = credited_fielders.get(index + 1)
+                        && next != fielder
+                        && !assisting_fielders.contains(fielder)
+                    {
+                        credits.push((*fielder, FieldingCreditType::Assist));
+                    }
+
+                    credits.push((*fielder, FieldingCreditType::Putout));
+                }
+
+                credits
+            },
+            EventType::Error { credited_fielder, assisting_fielders } => {
+                let mut credits = assisting_fielders
+                    .iter()
+                    .map(|fielder| (*fielder, FieldingCreditType::Assist))
+                    .collect::<Vec<(Fielder, FieldingCreditType)>>();
+
+                credits.push((*credited_fielder, FieldingCreditType::Error));
+                credits
+            },
+            EventType::ErrorOnFoulFlyBall { credited_fielder } => vec![(*credited_fielder, FieldingCreditType::Error)],
+            EventType::Strikeout { ball_path, base_running_event } => {
+                let mut credits = if ball_path.is_empty() {
+                    if batter_out { vec![(Fielder::Known(2), FieldingCreditType::Putout)] } else { Vec::new() }
+                } else {
+                    ball_path_credits(ball_path, batter_out)
+                };
+
+                if let Some(base_running_event) = base_running_event.as_ref() {
+                    credits.extend(base_running_event.fielding_credits(false));
+                }
+
+                credits
+            },
+            EventType::Walk { base_running_event, .. } => base_running_event
+                .as_ref()
+                .as_ref()
+                .map(|event_type| event_type.fielding_credits(false))
+                .unwrap_or_default(),
+            EventType::CaughtStealing { ball_path, .. } | EventType::Pickoff { ball_path, .. } => ball_path_credits(ball_path, true),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Display, EnumString, VariantNames)]
 pub enum PlayNote {
     #[strum(serialize = "#")]
     UncertainHash,
@@ -676,45 +1802,565 @@ pub enum PlayNote {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Play {
-    info_state: InfoState,
-    home_team_players: Vec<Player>,
-    visiting_team_players: Vec<Player>,
-    inning: u8,
-    batter_id: String,
-    count: Option<Count>,
-    pitches: Option<Vec<Pitch>>,
-    event: Option<Event>,
-    note: Option<PlayNote>,
-    comments: Vec<String>,
+    pub(crate) info_state: InfoState,
+    pub(crate) home_team_players: Vec<Player>,
+    pub(crate) visiting_team_players: Vec<Player>,
+    pub(crate) inning: u8,
+    pub(crate) team: Team,
+    pub(crate) batter_id: String,
+    pub(crate) count: Option<Count>,
+    pub(crate) pitches: Option<Vec<Pitch>>,
+    pub(crate) event: Option<Event>,
+    /// The event as written in the file.
+    pub(crate) event_text: String,
+    pub(crate) note: Option<PlayNote>,
+    pub(crate) comments: Vec<String>,
+    pub(crate) defense: Alignment,
+    pub(crate) outs: u8,
+    pub(crate) bases: [Option<BaseRunner>; 3],
+    pub(crate) runner_advances: Vec<RunnerAdvance>,
 }
 
-#[derive(Clone)]
+impl Play {
+    pub fn get_info_state(&self) -> &InfoState {
+        &self.info_state
+    }
+
+    pub fn get_home_team_players(&self) -> &Vec<Player> {
+        &self.home_team_players
+    }
+
+    pub fn get_visiting_team_players(&self) -> &Vec<Player> {
+        &self.visiting_team_players
+    }
+
+    pub fn get_inning(&self) -> u8 {
+        self.inning
+    }
+
+    /// The team at bat.
+    pub fn get_team(&self) -> Team {
+        self.team
+    }
+
+    pub fn get_batter_id(&self) -> &String {
+        &self.batter_id
+    }
+
+    pub fn get_count(&self) -> Option<&Count> {
+        self.count.as_ref()
+    }
+
+    pub fn get_pitches(&self) -> Option<&Vec<Pitch>> {
+        self.pitches.as_ref()
+    }
+
+    pub fn get_event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    pub fn get_event_text(&self) -> &String {
+        &self.event_text
+    }
+
+    /// Replaces the event, writing its text out afresh.
+    pub fn set_event(&mut self, event: Event) {
+        self.event_text = event.to_string();
+        self.event = Some(event);
+    }
+
+    pub fn get_note(&self) -> Option<&PlayNote> {
+        self.note.as_ref()
+    }
+
+    pub fn get_comments(&self) -> &Vec<String> {
+        &self.comments
+    }
+
+    /// The fielding team's alignment at the time of the play.
+    pub fn get_defense(&self) -> &Alignment {
+        &self.defense
+    }
+
+    pub fn get_pitcher_id(&self) -> Option<&String> {
+        self.defense.get(&Position::Pitcher)
+    }
+
+    /// Resolves a fielder number to the id of the player at that position during the play.
+    pub fn fielder_id(&self, fielder: &Fielder) -> Option<&String> {
+        self.defense.get_fielder(fielder)
+    }
+
+    /// The count after each pitch to the batter, skipping pickoff throws and other
+    /// non-pitches.
+    pub fn get_count_progression(&self) -> Vec<(&Pitch, Count)> {
+        let mut count = Count::Known { balls: 0, strikes: 0 };
+
+        self.pitches
+            .iter()
+            .flatten()
+            .filter(|pitch| pitch.pitch_type.is_pitch())
+            .map(|pitch| {
+                count = count.after(&pitch.pitch_type);
+                (pitch, count.clone())
+            })
+            .collect()
+    }
+
+    /// The count the play happened on according to the pitches. When the last pitch ends the
+    /// plate appearance, that's the count before it.
+    pub fn get_reconstructed_count(&self) -> Option<Count> {
+        let progression = self.get_count_progression();
+        let (last_pitch, last_count) = progression.last()?;
+
+        let ends_plate_appearance = last_pitch.pitch_type.is_in_play()
+            || last_pitch.pitch_type == PitchType::HitBatter
+            || last_count.is_complete();
+
+        if !ends_plate_appearance {
+            return Some(last_count.clone());
+        }
+
+        match progression.len() {
+            1 => Some(Count::Known { balls: 0, strikes: 0 }),
+            n => Some(progression[n - 2].1.clone()),
+        }
+    }
+
+    /// Compares the recorded count against the one reconstructed from the pitches, if both
+    /// are known.
+    pub fn check_count(&self) -> Option<CountMismatch> {
+        let recorded = self.count.as_ref()?;
+        let reconstructed = self.get_reconstructed_count()?;
+
+        if matches!(recorded, Count::Known { .. }) && *recorded != reconstructed {
+            return Some(CountMismatch {
+                recorded: recorded.clone(),
+                reconstructed,
+            });
+        }
+
+        None
+    }
+
+    /// The number of outs before the play.
+    pub fn get_outs(&self) -> u8 {
+        self.outs
+    }
+
+    /// The runners on first, second and third before the play.
+    pub fn get_bases(&self) -> &[Option<BaseRunner>; 3] {
+        &self.bases
+    }
+
+    pub fn get_runner(&self, base: &Base) -> Option<&BaseRunner> {
+        base.index().and_then(|index| self.bases[index].as_ref())
+    }
+
+    /// Every runner movement on the play, including the batter's.
+    pub fn get_runner_advances(&self) -> &Vec<RunnerAdvance> {
+        &self.runner_advances
+    }
+
+    pub fn get_runs(&self) -> u8 {
+        self.runner_advances
+            .iter()
+            .filter(|runner_advance| runner_advance.advance.is_run())
+            .count() as u8
+    }
+
+    /// Whether the advance drives in a run for the batter. An explicit `(RBI)` or `(NR)`
+    /// decides it. Otherwise runs count on hits, outs and fielder's choices, but not on ground
+    /// ball double plays or when an error lets the runner score. Walks, hit batters and
+    /// interference only drive in a runner forced home, and reaching on an error only a runner
+    /// from third with fewer than two outs.
+    pub fn credits_rbi(&self, advance: &Advance) -> bool {
+        if !advance.is_run() {
+            return false;
+        }
+
+        for parameter in &advance.parameters {
+            match parameter {
+                AdvanceParameter::RBICredited => return true,
+                AdvanceParameter::RBINotCredited => return false,
+                AdvanceParameter::BallPath(ball_path) if ball_path.iter().any(BallPathNode::is_error) => return false,
+                _ => {},
+            }
+        }
+
+        let Some(event) = &self.event else {
+            return false;
+        };
+
+        let grounded_into_double_play = event.modifiers.iter().any(|modifier| matches!(
+            modifier,
+            EventModifier::GroundBallDoublePlay
+                | EventModifier::GroundBallTriplePlay
+                | EventModifier::BuntGroundedIntoDoublePlay
+        ));
+
+        match &event.event_type {
+            event_type if event_type.forces_runners() => {
+                advance.starting_base == Base::Third && self.bases.iter().all(Option::is_some)
+            },
+            EventType::Out { .. }
+            | EventType::DoublePlay { .. }
+            | EventType::TriplePlay { .. }
+            | EventType::FieldersChoice { .. }
+            | EventType::Single { .. }
+            | EventType::Double { .. }
+            | EventType::GroundRuleDouble
+            | EventType::Triple { .. }
+            | EventType::SoloHomeRun
+            | EventType::InsideTheParkHomeRun { .. } => !grounded_into_double_play,
+            EventType::Error { .. } => advance.starting_base == Base::Third && self.outs < 2,
+            _ => false,
+        }
+    }
+
+    pub fn get_rbi(&self) -> u8 {
+        self.runner_advances
+            .iter()
+            .filter(|runner_advance| self.credits_rbi(&runner_advance.advance))
+            .count() as u8
+    }
+
+    /// The runners on base once the play is over.
+    pub fn get_bases_after(&self) -> [Option<BaseRunner>; 3] {
+        let mut bases = self.bases.clone();
+
+        for runner_advance in &self.runner_advances {
+            if let Some(index) = runner_advance.advance.starting_base.index() {
+                bases[index] = None;
+            }
+        }
+
+        for runner_advance in self.runner_advances.iter().filter(|ra| !ra.advance.out) {
+            if let Some(index) = runner_advance.advance.ending_base.index() {
+                bases[index] = Some(runner_advance.runner.clone());
+            }
+        }
+
+        bases
+    }
+
+    /// The pitches thrown during this play. A plate appearance interrupted by a base running
+    /// play repeats the earlier pitches before a `.`, so only those after the last one count.
+    pub fn get_pitches_thrown(&self) -> Vec<&Pitch> {
+        let Some(pitches) = &self.pitches else {
+            return Vec::new();
+        };
+
+        let start = pitches
+            .iter()
+            .rposition(|pitch| pitch.pitch_type == PitchType::NotInvolvingBatter)
+            .map_or(0, |index| index + 1);
+
+        pitches[start..].iter().filter(|pitch| pitch.pitch_type.is_pitch()).collect()
+    }
+
+    /// Every putout, assist and error on the play, including those on runner advances. A
+    /// fielder only gets one assist per play.
+    pub fn get_fielding_credits(&self) -> Vec<FieldingCredit> {
+        let Some(event) = &self.event else {
+            return Vec::new();
+        };
+
+        let batter_out = self.runner_advances
+            .iter()
+            .any(|ra| ra.advance.starting_base == Base::Home && ra.advance.out);
+
+        let mut credits = event.event_type.fielding_credits(batter_out);
+
+        for advance in &event.advances {
+            for parameter in &advance.parameters {
+                if let AdvanceParameter::BallPath(ball_path) = parameter {
+                    credits.extend(ball_path_credits(ball_path, advance.out));
+                }
+            }
+        }
+
+        for modifier in &event.modifiers {
+            if let EventModifier::Error(fielder) = modifier {
+                credits.push((*fielder, FieldingCreditType::Error));
+            }
+        }
+
+        let mut fielding_credits = Vec::<FieldingCredit>::new();
+        for (fielder, credit_type) in credits {
+            if credit_type == FieldingCreditType::Assist
+                && fielding_credits.iter().any(|c| c.fielder == fielder && c.credit_type == credit_type)
+            {
+                continue;
+            }
+
+            fielding_credits.push(FieldingCredit {
+                fielder,
+                player_id: self.fielder_id(&fielder).cloned(),
+                credit_type,
+            });
+        }
+
+        fielding_credits
+    }
+}
+
+/// A `start` or `sub` record, placed among the plays.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineupEntry {
+    pub is_start: bool,
+    pub player_id: String,
+    pub player_name: String,
+    pub team: Team,
+    pub batting_order: u8,
+    pub position: Position,
+    /// The number of plays recorded before it.
+    pub play_index: usize,
+}
+
+/// A `radj`, `badj`, `padj` or `ladj` record.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Adjustment {
+    /// A runner placed on base to start the next half inning, as in extra innings from 2020.
+    Runner { player_id: String, base: Base },
+    /// A batter batting from the side they don't usually bat from.
+    BattingHand { player_id: String, hand: Hand },
+    /// A pitcher throwing with the hand they don't usually throw with.
+    PitchingHand { player_id: String, hand: Hand },
+    /// A team batting out of order, with the batting order slot that came up.
+    Lineup { team: Team, batting_order: u8 },
+}
+
+/// An adjustment record, placed among the plays.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdjustmentEntry {
+    pub adjustment: Adjustment,
+    /// The number of plays recorded before it.
+    pub play_index: usize,
+}
+
+/// Runs scored by each team in every inning they batted.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineScore {
+    pub visiting: Vec<u8>,
+    pub home: Vec<u8>,
+}
+
+impl LineScore {
+    pub fn get_runs(&self, team: &Team) -> u8 {
+        match team {
+            Team::Visiting => self.visiting.iter().sum(),
+            Team::Home => self.home.iter().sum(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     id: String,
     version: u8,
+    info_state: InfoState,
+    /// The info records as they were written, in order.
+    info_records: Vec<Info>,
+    lineup_entries: Vec<LineupEntry>,
+    adjustments: Vec<AdjustmentEntry>,
+    /// Comments that come before the first play. Later ones belong to the play before them.
+    comments: Vec<String>,
     plays: Vec<Play>,
+    earned_runs: Vec<(String, u8)>,
+}
+
+impl Game {
+    pub fn get_id(&self) -> &String {
+        &self.id
+    }
+
+    pub fn get_version(&self) -> u8 {
+        self.version
+    }
+
+    /// The year the game was played, from its date or else from its id, as in `ANA201004050`.
+    pub fn get_season(&self) -> Option<u16> {
+        let from_date = self.info_state.date.as_ref().and_then(|date| date.get(..4));
+        from_date
+            .or(self.id.get(3..7))
+            .and_then(|year| year.parse().ok())
+    }
+
+    /// The game's info records, as they stood at the end of the file.
+    pub fn get_info_state(&self) -> &InfoState {
+        &self.info_state
+    }
+
+    pub fn get_info_records(&self) -> &Vec<Info> {
+        &self.info_records
+    }
+
+    pub fn get_lineup_entries(&self) -> &Vec<LineupEntry> {
+        &self.lineup_entries
+    }
+
+    pub fn get_adjustments(&self) -> &Vec<AdjustmentEntry> {
+        &self.adjustments
+    }
+
+    /// The team's starters, in the order of their `start` records.
+    pub fn get_starters(&self, team: &Team) -> Vec<&LineupEntry> {
+        self.lineup_entries
+            .iter()
+            .filter(|entry| entry.is_start && entry.team == *team)
+            .collect()
+    }
+
+    pub fn get_comments(&self) -> &Vec<String> {
+        &self.comments
+    }
+
+    pub fn get_plays(&self) -> &Vec<Play> {
+        &self.plays
+    }
+
+    /// The plays, for correcting events before writing the game back out. The base and
+    /// out state of later plays isn't updated until the written file is parsed again.
+    pub fn get_plays_mut(&mut self) -> &mut Vec<Play> {
+        &mut self.plays
+    }
+
+    /// Runs by inning for both teams. A team that didn't bat in an inning has no entry for
+    /// it, so the home team usually has one fewer inning when it wins.
+    pub fn get_line_score(&self) -> LineScore {
+        let mut line_score = LineScore::default();
+
+        for play in &self.plays {
+            let innings = match play.team {
+                Team::Visiting => &mut line_score.visiting,
+                Team::Home => &mut line_score.home,
+            };
+
+            let inning = play.inning as usize;
+            if innings.len() < inning {
+                innings.resize(inning, 0);
+            }
+
+            innings[inning - 1] += play.get_runs();
+        }
+
+        line_score
+    }
+
+    /// The earned runs recorded against each pitcher in the file's `data,er` records.
+    pub fn get_earned_runs(&self) -> &Vec<(String, u8)> {
+        &self.earned_runs
+    }
+
+    /// Every play whose recorded count disagrees with its pitches, by index.
+    pub fn get_count_mismatches(&self) -> Vec<(usize, CountMismatch)> {
+        self.plays
+            .iter()
+            .enumerate()
+            .filter_map(|(index, play)| play.check_count().map(|mismatch| (index, mismatch)))
+            .collect()
+    }
+
+    /// Runs charged to each pitcher, along with the runners they inherited and how many of
+    /// those scored, in the order the pitchers appeared.
+    pub fn get_pitcher_responsibilities(&self) -> Vec<PitcherResponsibility> {
+        let mut responsibilities = Vec::<PitcherResponsibility>::new();
+
+        for play in &self.plays {
+            let Some(pitcher_id) = play.get_pitcher_id() else {
+                continue;
+            };
+
+            if responsibilities.iter().all(|r| &r.pitcher_id != pitcher_id) {
+                let inherited_runners = play.bases
+                    .iter()
+                    .flatten()
+                    .filter(|runner| runner.inherited_by.as_ref() == Some(pitcher_id))
+                    .count() as u8;
+
+                responsibilities.push(PitcherResponsibility {
+                    pitcher_id: pitcher_id.to_string(),
+                    inherited_runners,
+                    ..Default::default()
+                });
+            }
+
+            for runner_advance in play.runner_advances.iter().filter(|ra| ra.advance.is_run()) {
+                let runner = &runner_advance.runner;
+
+                if let Some(r) = responsibilities.iter_mut().find(|r| Some(&r.pitcher_id) == runner.pitcher_id.as_ref()) {
+                    r.runs_allowed += 1;
+                }
+
+                if let Some(r) = responsibilities.iter_mut().find(|r| Some(&r.pitcher_id) == runner.inherited_by.as_ref()) {
+                    r.inherited_runners_scored += 1;
+                }
+            }
+        }
+
+        responsibilities
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountMismatch {
+    pub recorded: Count,
+    pub reconstructed: Count,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitcherResponsibility {
+    pub pitcher_id: String,
+    pub runs_allowed: u8,
+    pub inherited_runners: u8,
+    pub inherited_runners_scored: u8,
 }
 
 #[derive(Clone)]
 pub struct GameBuilder {
     pub info_state: InfoState,
+    pub info_records: Vec<Info>,
     pub home_team_players: Vec<Player>,
     pub visiting_team_players: Vec<Player>,
     pub id: Option<String>,
     pub version: Option<u8>,
+    pub lineup_entries: Vec<LineupEntry>,
+    pub adjustments: Vec<AdjustmentEntry>,
+    pub comments: Vec<String>,
     pub plays: Vec<Play>,
+    pub earned_runs: Vec<(String, u8)>,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameBuilder {
     pub fn new() -> Self {
         Self {
             info_state: InfoState::default(),
+            info_records: Vec::new(),
             home_team_players: Vec::new(),
             visiting_team_players: Vec::new(),
             id: None,
             version: None,
+            lineup_entries: Vec::new(),
+            adjustments: Vec::new(),
+            comments: Vec::new(),
             plays: Vec::new(),
+            earned_runs: Vec::new(),
         }
     }
 
@@ -879,6 +2525,7 @@ impl GameBuilder {
     }
 
     pub fn set_info(&mut self, info: Info) {
+        self.info_records.push(info.clone());
         match info {
             Info::VisitingTeam(visiting_team) => self.set_info_state_visiting_team(visiting_team),
             Info::HomeTeam(home_team) => self.set_info_state_home_team(home_team),
@@ -941,10 +2588,33 @@ impl GameBuilder {
         }
     }
 
+    pub fn lineup_entry(&mut self, lineup_entry: LineupEntry) {
+        self.lineup_entries.push(lineup_entry);
+    }
+
+    pub fn adjustment(&mut self, adjustment: Adjustment) {
+        self.adjustments.push(AdjustmentEntry {
+            adjustment,
+            play_index: self.plays.len(),
+        });
+    }
+
+    /// Attaches the comment to the latest play, or to the game if there isn't one yet.
+    pub fn comment(&mut self, comment: String) {
+        match self.plays.last_mut() {
+            Some(play) => play.comments.push(comment),
+            None => self.comments.push(comment),
+        }
+    }
+
     pub fn play(&mut self, play: Play) {
         self.plays.push(play);
     }
 
+    pub fn earned_runs(&mut self, player_id: String, earned_runs: u8) {
+        self.earned_runs.push((player_id, earned_runs));
+    }
+
     pub fn build(self) -> Result<Game, String> {
         let id = self.id.ok_or("id is required")?;
         let version = self.version.ok_or("version is required")?;
@@ -958,7 +2628,310 @@ impl GameBuilder {
         Ok(Game {
             id,
             version,
+            info_state: self.info_state,
+            info_records: self.info_records,
+            lineup_entries: self.lineup_entries,
+            adjustments: self.adjustments,
+            comments: self.comments,
             plays: plays?,
+            earned_runs: self.earned_runs,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::FileParser;
+
+    fn parse_plays(plays: &str) -> Game {
+        let file = format!("id,CIN199004090
+version,2
+info,visteam,HOU
+info,hometeam,CIN
+start,youngg001,\"Gerald Young\",0,1,8
+start,rijoj001,\"Jose Rijo\",1,0,1
+{plays}");
+
+        FileParser::new().parse_file(&file).unwrap().remove(0)
+    }
+
+    mod count {
+        use super::*;
+
+        fn known(balls: u8, strikes: u8) -> Count {
+            Count::Known { balls, strikes }
+        }
+
+        #[test]
+        fn count_after_pitch() {
+            assert_eq!(known(0, 0).after(&PitchType::Ball), known(1, 0));
+            assert_eq!(known(0, 0).after(&PitchType::CalledStrike), known(0, 1));
+            assert_eq!(known(1, 2).after(&PitchType::Foul), known(1, 2));
+            assert_eq!(known(1, 2).after(&PitchType::FoulTip), known(1, 3));
+            assert_eq!(known(1, 2).after(&PitchType::FoulBunt), known(1, 3));
+            assert_eq!(known(1, 1).after(&PitchType::BallInPlayByBatter), known(1, 1));
+            assert_eq!(known(1, 1).after(&PitchType::PickoffThrowToFirstBase), known(1, 1));
+            assert_eq!(Count::Unknown.after(&PitchType::Ball), Count::Unknown);
+        }
+
+        #[test]
+        fn count_progression_skips_non_pitches() {
+            let game = parse_plays("play,1,0,youngg001,12,B1C>F+2FSX,8/F");
+            let play = &game.get_plays()[0];
+
+            let counts = play.get_count_progression()
+                .into_iter()
+                .map(|(_, count)| count)
+                .collect::<Vec<Count>>();
+            assert_eq!(counts, vec![known(1, 0), known(1, 1), known(1, 2), known(1, 2), known(1, 3), known(1, 3)]);
+        }
+
+        #[test]
+        fn reconstructed_count_matches_recorded() {
+            // the recorded count is the one before the last pitch of the plate appearance
+            let game = parse_plays("play,1,0,youngg001,32,BBCBFFB,W
+play,1,0,youngg001,12,BCFS,K
+play,1,0,youngg001,01,CX,8/F");
+            assert!(game.get_count_mismatches().is_empty());
+            assert_eq!(game.get_plays()[0].get_reconstructed_count(), Some(known(3, 2)));
+        }
+
+        #[test]
+        fn flag_count_mismatches() {
+            let game = parse_plays("play,1,0,youngg001,10,BX,S7
+play,1,0,youngg001,22,BX,8/F
+play,1,0,youngg001,??,BX,8/F");
+
+            assert_eq!(game.get_count_mismatches(), vec![(1, CountMismatch {
+                recorded: known(2, 2),
+                reconstructed: known(1, 0),
+            })]);
+        }
+    }
+
+    mod event_classification {
+        use super::*;
+
+        fn event(event_type: EventType, modifiers: Vec<EventModifier>) -> Event {
+            Event {
+                event_type,
+                modifiers,
+                advances: Vec::new(),
+            }
+        }
+
+        fn known(fielder: u8) -> Fielder {
+            Fielder::Known(fielder)
+        }
+
+        /// Every event type alongside whether it's a plate appearance, an at bat and a hit,
+        /// its total bases and the outs it records.
+        fn every_event_type() -> Vec<(EventType, bool, bool, bool, u8, u8)> {
+            vec![
+                (EventType::Out { credited_fielder: known(3), assisting_fielders: vec![known(6)], runner_out: Runner::Batter }, true, true, false, 0, 1),
+                (EventType::DoublePlay { credited_fielders: vec![known(4), known(3)], assisting_fielders: vec![known(6)], runners_out: vec![Runner::First, Runner::Batter] }, true, true, false, 0, 2),
+                (EventType::TriplePlay { credited_fielders: vec![known(5), known(4), known(3)], assisting_fielders: Vec::new(), runners_out: vec![Runner::Second, Runner::First, Runner::Batter] }, true, true, false, 0, 3),
+                (EventType::Interference, true, false, false, 0, 0),
+                (EventType::Single { credited_fielder: known(7), assisting_fielders: Vec::new() }, true, true, true, 1, 0),
+                (EventType::Double { credited_fielder: known(8), assisting_fielders: Vec::new() }, true, true, true, 2, 0),
+                (EventType::GroundRuleDouble, true, true, true, 2, 0),
+                (EventType::Triple { credited_fielder: known(9), assisting_fielders: Vec::new() }, true, true, true, 3, 0),
+                (EventType::Error { credited_fielder: known(6), assisting_fielders: Vec::new() }, true, true, false, 0, 0),
+                (EventType::FieldersChoice { credited_fielder: known(6) }, true, true, false, 0, 0),
+                (EventType::ErrorOnFoulFlyBall { credited_fielder: known(5) }, false, false, false, 0, 0),
+                (EventType::SoloHomeRun, true, true, true, 4, 0),
+                (EventType::InsideTheParkHomeRun { credited_fielder: known(8) }, true, true, true, 4, 0),
+                (EventType::HitByPitch, true, false, false, 0, 0),
+                (EventType::Strikeout { ball_path: Vec::new(), base_running_event: Box::new(None) }, true, true, false, 0, 1),
+                (EventType::Strikeout { ball_path: Vec::new(), base_running_event: Box::new(Some(EventType::CaughtStealing { base: Base::Second, ball_path: Vec::new() })) }, true, true, false, 0, 2),
+                (EventType::NoPlay, false, false, false, 0, 0),
+                (EventType::Walk { intentional: false, base_running_event: Box::new(None) }, true, false, false, 0, 0),
+                (EventType::Walk { intentional: true, base_running_event: Box::new(Some(EventType::StolenBase { bases: vec![Base::Third] })) }, true, false, false, 0, 0),
+                (EventType::Balk, false, false, false, 0, 0),
+                (EventType::CaughtStealing { base: Base::Second, ball_path: vec![BallPathNode::Success { fielder: known(2) }, BallPathNode::Success { fielder: known(6) }] }, false, false, false, 0, 1),
+                (EventType::CaughtStealing { base: Base::Second, ball_path: vec![BallPathNode::Error { fielder: known(6), error_type: FieldingErrorType::NonThrowingError }] }, false, false, false, 0, 0),
+                (EventType::DefensiveIndifference, false, false, false, 0, 0),
+                (EventType::OtherAdvance, false, false, false, 0, 0),
+                (EventType::PassedBall, false, false, false, 0, 0),
+                (EventType::WildPitch, false, false, false, 0, 0),
+                (EventType::Pickoff { caught_stealing: false, base: Base::First, ball_path: vec![BallPathNode::Success { fielder: known(1) }, BallPathNode::Success { fielder: known(3) }] }, false, false, false, 0, 1),
+                (EventType::Pickoff { caught_stealing: true, base: Base::Second, ball_path: vec![BallPathNode::Success { fielder: known(1) }, BallPathNode::Success { fielder: known(6) }] }, false, false, false, 0, 1),
+                (EventType::StolenBase { bases: vec![Base::Second, Base::Home] }, false, false, false, 0, 0),
+            ]
+        }
+
+        #[test]
+        fn classify_every_event_type() {
+            for (event_type, plate_appearance, at_bat, hit, total_bases, outs) in every_event_type() {
+                let event = event(event_type.clone(), Vec::new());
+
+                assert_eq!(event_type.is_plate_appearance(), plate_appearance, "{event_type:?}");
+                assert_eq!(event_type.ends_plate_appearance(), plate_appearance, "{event_type:?}");
+                assert_eq!(event.is_at_bat(), at_bat, "{event_type:?}");
+                assert_eq!(event_type.is_hit(), hit, "{event_type:?}");
+                assert_eq!(event_type.total_bases(), total_bases, "{event_type:?}");
+                assert_eq!(event.outs_recorded(), outs, "{event_type:?}");
+                assert!(!event.is_sacrifice(), "{event_type:?}");
+            }
+        }
+
+        #[test]
+        fn sacrifices_are_not_at_bats() {
+            let sacrifice_fly = event(
+                EventType::Out { credited_fielder: known(9), assisting_fielders: Vec::new(), runner_out: Runner::Batter },
+                vec![EventModifier::SacrificeFly, EventModifier::BattedBall(BattedBall { trajectory: Some(Trajectory::Fly), location: None })],
+            );
+            assert!(sacrifice_fly.is_sacrifice());
+            assert!(sacrifice_fly.event_type.is_plate_appearance());
+            assert!(!sacrifice_fly.is_at_bat());
+
+            let sacrifice_bunt_error = event(
+                EventType::Error { credited_fielder: known(1), assisting_fielders: Vec::new() },
+                vec![EventModifier::SacrificeHitOrBunt],
+            );
+            assert!(sacrifice_bunt_error.is_sacrifice());
+            assert!(!sacrifice_bunt_error.is_at_bat());
+
+            // a sacrifice modifier on a base running event doesn't make it a sacrifice
+            let stolen_base = event(EventType::StolenBase { bases: vec![Base::Second] }, vec![EventModifier::SacrificeHitOrBunt]);
+            assert!(!stolen_base.is_sacrifice());
+        }
+
+        #[test]
+        fn written_advances_count_towards_outs() {
+            let mut double = event(EventType::Double { credited_fielder: known(7), assisting_fielders: Vec::new() }, Vec::new());
+            double.advances = vec![
+                Advance::new(Base::First, Base::Home, true),
+                Advance {
+                    parameters: vec![AdvanceParameter::BallPath(vec![BallPathNode::Error { fielder: known(2), error_type: FieldingErrorType::NonThrowingError }])],
+                    ..Advance::new(Base::Second, Base::Home, true)
+                },
+            ];
+            assert_eq!(double.outs_recorded(), 1);
+        }
+    }
+
+    mod double_plays {
+        use super::*;
+
+        #[test]
+        fn double_plays_are_marked_by_the_scorer() {
+            let game = parse_plays("play,1,0,youngg001,00,X,D7
+play,1,0,youngg001,00,X,8/F8.2X3(85)
+play,2,0,youngg001,00,X,S7
+play,2,0,youngg001,00,X,64(1)3/GDP
+play,3,0,youngg001,00,X,S7
+play,3,0,youngg001,00,X,S8.1-2
+play,3,0,youngg001,00,X,1(B)16(2)63(1)/LTP
+");
+            let events = game.get_plays().iter().map(|play| play.get_event().unwrap()).collect::<Vec<_>>();
+
+            // two outs on a play aren't a double play unless the scorer says so
+            assert_eq!(events[1].outs_recorded(), 2);
+            assert!(!events[1].is_double_play());
+            assert!(events[3].is_double_play());
+            assert!(events[6].is_triple_play() && !events[6].is_double_play());
+        }
+    }
+
+    mod rbi {
+        use super::*;
+
+        #[test]
+        fn walks_only_drive_in_forced_runs() {
+            let game = parse_plays("play,1,0,youngg001,00,X,T8
+play,1,0,youngg001,00,B,W+WP.3-H
+play,1,0,youngg001,00,X,T8
+play,1,0,youngg001,00,S,K+WP.3-H
+play,2,0,youngg001,00,B,W
+play,2,0,youngg001,00,B,W
+play,2,0,youngg001,00,B,W
+play,2,0,youngg001,00,B,W.3-H
+");
+            let rbi = game.get_plays().iter().map(Play::get_rbi).collect::<Vec<_>>();
+
+            // with first base open the runner scores on the wild pitch, not the walk
+            assert_eq!(rbi, vec![0, 0, 0, 0, 0, 0, 0, 1]);
+        }
+    }
+
+    mod field_location {
+        use super::*;
+        use strum::VariantNames;
+
+        #[test]
+        fn sides_and_depths() {
+            let cases = [
+                ("7LSF", FieldSide::Left, FieldDepth::Short, true),
+                ("78XD", FieldSide::Left, FieldDepth::ExtremelyDeep, true),
+                ("8", FieldSide::Center, FieldDepth::Normal, true),
+                ("89D", FieldSide::Right, FieldDepth::Deep, true),
+                ("56D", FieldSide::Left, FieldDepth::Deep, false),
+                ("6MS", FieldSide::Center, FieldDepth::Short, false),
+                ("4", FieldSide::Right, FieldDepth::Normal, false),
+                ("15", FieldSide::Left, FieldDepth::Normal, false),
+                ("23F", FieldSide::Right, FieldDepth::Normal, false),
+                ("2F", FieldSide::Center, FieldDepth::Normal, false),
+            ];
+
+            for (code, side, depth, outfield) in cases {
+                let location = code.parse::<FieldLocation>().unwrap();
+                assert_eq!((location.get_side(), location.get_depth(), location.is_outfield()), (side, depth, outfield), "{code}");
+            }
+        }
+
+        #[test]
+        fn depths_follow_codes() {
+            for code in FieldLocation::VARIANTS {
+                let location = code.parse::<FieldLocation>().unwrap();
+                let depth = if code.contains("XD") {
+                    FieldDepth::ExtremelyDeep
+                } else if code.contains('D') {
+                    FieldDepth::Deep
+                } else if code.contains('S') {
+                    FieldDepth::Short
+                } else {
+                    FieldDepth::Normal
+                };
+                assert_eq!(location.get_depth(), depth, "{code}");
+                assert_eq!(location.is_outfield(), code.starts_with(['7', '8', '9']), "{code}");
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_representation {
+        use super::*;
+
+        #[test]
+        fn codes_serialize_as_retrosheet_strings() {
+            let event = Event {
+                event_type: EventType::StolenBase { bases: vec![Base::Home] },
+                modifiers: vec![EventModifier::Error(Fielder::Known(2)), EventModifier::BattedBall(BattedBall { trajectory: None, location: Some(FieldLocation::DeepCenterLeft) })],
+                advances: vec![Advance::new(Base::Third, Base::Home, false)],
+            };
+
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["event_type"], serde_json::json!({ "StolenBase": { "bases": ["H"] } }));
+            assert_eq!(json["modifiers"], serde_json::json!(["E2", "78D"]));
+            assert_eq!(json["advances"][0]["starting_base"], "3");
+
+            assert_eq!(serde_json::to_value(Count::Known { balls: 3, strikes: 2 }).unwrap(), "32");
+            assert_eq!(serde_json::to_value(Team::Home).unwrap(), "1");
+            assert_eq!(serde_json::to_value(Position::DesignatedHitter).unwrap(), "10");
+            assert!(serde_json::from_str::<Fielder>("\"0\"").is_err());
+        }
+
+        #[test]
+        fn game_round_trips_through_json() {
+            let game = parse_plays("play,1,0,youngg001,12,CBFX,S7/L7LD+
+com,\"Young hit it off the wall\"
+play,1,0,youngg001,??,,CS2(26)
+");
+
+            let json = serde_json::to_string(&game).unwrap();
+            assert_eq!(serde_json::from_str::<Game>(&json).unwrap(), game);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::game::Hand;
+
+/// A player as listed in a Retrosheet roster file, one line per player:
+/// `id,last name,first name,bats,throws,team,position`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RosterEntry {
+    pub id: String,
+    pub last_name: String,
+    pub first_name: String,
+    /// Unknown when the file has `?`.
+    pub bats: Option<Hand>,
+    pub throws: Option<Hand>,
+    pub team: String,
+    pub position: String,
+}
+
+/// Players from any number of roster files, keyed by id.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Roster {
+    players: HashMap<String, RosterEntry>,
+}
+
+impl Roster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every player in a roster file, replacing any already listed under the same id.
+    pub fn parse_file(&mut self, file_content: &str) -> Result<(), String> {
+        for line in file_content.lines().map(str::trim_end).filter(|line| !line.is_empty()) {
+            let fields = line.split(',').collect::<Vec<&str>>();
+            let [id, last_name, first_name, bats, throws, team, position] = fields.as_slice() else {
+                return Err(format!("Invalid roster line: {line}"));
+            };
+
+            let hand = |hand: &str| match hand {
+                "?" | "" => Ok(None),
+                _ => hand.parse().map(Some).map_err(|_| format!("Invalid hand in roster line: {line}")),
+            };
+
+            self.players.insert(id.to_string(), RosterEntry {
+                id: id.to_string(),
+                last_name: last_name.to_string(),
+                first_name: first_name.to_string(),
+                bats: hand(bats)?,
+                throws: hand(throws)?,
+                team: team.to_string(),
+                position: position.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn get_player(&self, player_id: &str) -> Option<&RosterEntry> {
+        self.players.get(player_id)
+    }
+
+    pub fn get_throwing_hand(&self, player_id: &str) -> Option<Hand> {
+        self.get_player(player_id).and_then(|player| player.throws)
+    }
+
+    /// The side the batter hits from against the pitcher. A switch hitter bats opposite the
+    /// pitcher's throwing hand, and stays `Both` if that isn't known.
+    pub fn get_batting_hand(&self, batter_id: &str, pitcher_id: Option<&str>) -> Option<Hand> {
+        match self.get_player(batter_id)?.bats? {
+            Hand::Both => Some(pitcher_id
+                .and_then(|pitcher_id| self.get_throwing_hand(pitcher_id))
+                .map(|throws| throws.opposite())
+                .unwrap_or(Hand::Both)),
+            bats => Some(bats),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROSTER: &str = "spand001,Span,Denard,L,L,MIN,OF
+hudso001,Hudson,Orlando,B,R,MIN,2B
+bakes002,Baker,Scott,R,R,MIN,P
+weavj003,Weaver,Jered,R,R,ANA,P
+santj003,Santana,Johan,L,L,NYN,P
+";
+
+    #[test]
+    fn parse_roster_file() {
+        let mut roster = Roster::new();
+        roster.parse_file(ROSTER).unwrap();
+
+        let span = roster.get_player("spand001").unwrap();
+        assert_eq!(span.first_name, "Denard");
+        assert_eq!(span.bats, Some(Hand::Left));
+        assert_eq!(roster.get_throwing_hand("bakes002"), Some(Hand::Right));
+        assert!(roster.get_player("mauej001").is_none());
+
+        assert!(Roster::new().parse_file("spand001,Span,Denard,L,L,MIN").is_err());
+        assert!(Roster::new().parse_file("spand001,Span,Denard,X,L,MIN,OF").is_err());
+    }
+
+    #[test]
+    fn switch_hitters_bat_opposite_the_pitcher() {
+        let mut roster = Roster::new();
+        roster.parse_file(ROSTER).unwrap();
+
+        assert_eq!(roster.get_batting_hand("hudso001", Some("weavj003")), Some(Hand::Left));
+        assert_eq!(roster.get_batting_hand("hudso001", Some("santj003")), Some(Hand::Right));
+        assert_eq!(roster.get_batting_hand("hudso001", None), Some(Hand::Both));
+        assert_eq!(roster.get_batting_hand("spand001", Some("santj003")), Some(Hand::Left));
+    }
+}
@@ -0,0 +1,182 @@
+use super::game::{Advance, Alignment, Base, BaseRunner, Event, EventType, Position, Runner, RunnerAdvance, Team};
+
+#[derive(Clone, Debug, Default)]
+pub struct TeamState {
+    /// Player ids by batting order, where 0 is a pitcher who doesn't bat.
+    pub lineup: [Option<String>; 10],
+    pub defense: Alignment,
+}
+
+/// The live state of a game as its lines are read in order.
+#[derive(Clone, Debug, Default)]
+pub struct GameState {
+    pub visiting: TeamState,
+    pub home: TeamState,
+    pub half_inning: Option<(u8, Team)>,
+    pub outs: u8,
+    pub bases: [Option<BaseRunner>; 3],
+    /// Runners from `radj` records, put on base when the next half inning starts.
+    pub placed_runners: Vec<(String, Base)>,
+}
+
+impl GameState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn team(&self, team: &Team) -> &TeamState {
+        match team {
+            Team::Visiting => &self.visiting,
+            Team::Home => &self.home,
+        }
+    }
+
+    fn team_mut(&mut self, team: &Team) -> &mut TeamState {
+        match team {
+            Team::Visiting => &mut self.visiting,
+            Team::Home => &mut self.home,
+        }
+    }
+
+    pub fn substitute(&mut self, team: &Team, player_id: &str, batting_order: u8, position: Position) {
+        // a new pitcher mid-inning inherits the runners on base, who stay charged to
+        // whoever put them there
+        let fielding = self.half_inning.is_some_and(|(_, batting_team)| batting_team == team.opponent());
+        let replaces_pitcher = self.team(team).defense.get(&Position::Pitcher).is_some_and(|id| id != player_id);
+        if position == Position::Pitcher && fielding && replaces_pitcher && self.outs < 3 {
+            for runner in self.bases.iter_mut().flatten() {
+                runner.inherited_by = Some(player_id.to_string());
+            }
+        }
+
+        let team_state = self.team_mut(team);
+        let replaced_id = team_state.lineup[batting_order as usize].replace(player_id.to_string());
+        team_state.defense.set(position, player_id.to_string());
+
+        // a pinch runner takes over from the runner in their batting order slot
+        if position == Position::PinchRunner {
+            let runner = self.bases
+                .iter_mut()
+                .flatten()
+                .map(|runner| &mut runner.id)
+                .chain(self.placed_runners.iter_mut().map(|(id, _)| id))
+                .find(|id| Some(&**id) == replaced_id.as_ref());
+
+            if let Some(id) = runner {
+                *id = player_id.to_string();
+            }
+        }
+    }
+
+    /// Holds a runner from a `radj` record until the half inning they start on base in.
+    pub fn place_runner(&mut self, player_id: &str, base: Base) {
+        self.placed_runners.push((player_id.to_string(), base));
+    }
+
+    /// Clears the bases and outs if the play begins a new half inning, then puts any placed
+    /// runners on base, charged to the pitcher starting the half inning.
+    pub fn start_play(&mut self, inning: u8, team: Team) {
+        if self.half_inning != Some((inning, team)) {
+            self.half_inning = Some((inning, team));
+            self.outs = 0;
+            self.bases = Default::default();
+        }
+
+        let pitcher_id = self.team(&team.opponent()).defense.get(&Position::Pitcher).cloned();
+        for (id, base) in std::mem::take(&mut self.placed_runners) {
+            if let Some(index) = base.index() {
+                self.bases[index] = Some(BaseRunner {
+                    id,
+                    pitcher_id: pitcher_id.clone(),
+                    inherited_by: None,
+                });
+            }
+        }
+    }
+
+    /// Applies the event to the bases and outs, returning every runner movement it caused.
+    pub fn apply(&mut self, event: &Event, batter_id: &str, pitcher_id: Option<&String>) -> Result<Vec<RunnerAdvance>, String> {
+        let mut advances = event.get_all_advances();
+
+        if event.event_type.forces_runners() {
+            let mut base = Base::First;
+            while let Some(index) = base.index() {
+                if self.bases[index].is_none() {
+                    break;
+                }
+
+                if advances.iter().all(|a| a.starting_base != base) {
+                    advances.push(Advance::new(base, base.next(), false));
+                }
+
+                base = base.next();
+            }
+        }
+
+        let mut runner_advances = Vec::new();
+        for advance in advances {
+            let runner = match advance.starting_base.index() {
+                Some(index) => self.bases[index]
+                    .clone()
+                    .ok_or(format!("no runner on {:?} to advance", advance.starting_base))?,
+                None => BaseRunner {
+                    id: batter_id.to_string(),
+                    pitcher_id: pitcher_id.cloned(),
+                    inherited_by: None,
+                },
+            };
+
+            runner_advances.push(RunnerAdvance { runner, advance });
+        }
+
+        if replaces_runner(&event.event_type) {
+            swap_responsibility(&mut runner_advances, pitcher_id);
+        }
+
+        for runner_advance in &runner_advances {
+            if let Some(index) = runner_advance.advance.starting_base.index() {
+                self.bases[index] = None;
+            }
+        }
+
+        for runner_advance in &runner_advances {
+            if runner_advance.advance.out {
+                self.outs += 1;
+            } else if let Some(index) = runner_advance.advance.ending_base.index() {
+                self.bases[index] = Some(runner_advance.runner.clone());
+            }
+        }
+
+        Ok(runner_advances)
+    }
+}
+
+/// Whether the batter can reach base because a runner was put out instead.
+fn replaces_runner(event_type: &EventType) -> bool {
+    match event_type {
+        EventType::Out { runner_out, .. } => *runner_out != Runner::Batter,
+        EventType::DoublePlay { runners_out, .. } | EventType::TriplePlay { runners_out, .. } => !runners_out.contains(&Runner::Batter),
+        EventType::FieldersChoice { .. } => true,
+        _ => false,
+    }
+}
+
+/// When a batter reaches on a fielder's choice that puts out a runner charged to an earlier
+/// pitcher, the batter is charged to that pitcher in the runner's place.
+fn swap_responsibility(runner_advances: &mut [RunnerAdvance], pitcher_id: Option<&String>) {
+    let replaced = runner_advances
+        .iter()
+        .filter(|ra| ra.advance.starting_base != Base::Home && ra.advance.out)
+        .filter(|ra| ra.runner.pitcher_id.as_ref() != pitcher_id)
+        .max_by_key(|ra| ra.advance.starting_base.index())
+        .map(|ra| ra.runner.clone());
+
+    let batter = runner_advances
+        .iter_mut()
+        .find(|ra| ra.advance.starting_base == Base::Home && !ra.advance.out);
+
+    if let (Some(replaced), Some(batter)) = (replaced, batter) {
+        batter.runner.pitcher_id = replaced.pitcher_id;
+        batter.runner.inherited_by = replaced.inherited_by;
+    }
+}
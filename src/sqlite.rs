@@ -0,0 +1,373 @@
+use rusqlite::{Connection, Transaction, params};
+
+use crate::chadwick::EventCategory;
+use crate::parser::FileParser;
+use crate::parser::game::{AttendanceInfo, Base, FieldingCreditType, Game, Play, Team, TemperatureInfo, TimeOfGameInfo};
+
+/// Bases are stored as Retrosheet writes them, with the batter starting from `B` and runs
+/// scoring at `H`.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    game_id TEXT PRIMARY KEY,
+    date TEXT,
+    game_number INTEGER,
+    visiting_team TEXT,
+    home_team TEXT,
+    site TEXT,
+    start_time TEXT,
+    day_night TEXT,
+    innings INTEGER,
+    used_dh INTEGER,
+    temperature INTEGER,
+    time_of_game INTEGER,
+    attendance INTEGER,
+    winning_pitcher_id TEXT,
+    losing_pitcher_id TEXT,
+    save_pitcher_id TEXT,
+    game_type TEXT,
+    visiting_score INTEGER NOT NULL,
+    home_score INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS players (
+    player_id TEXT PRIMARY KEY,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS lineups (
+    game_id TEXT NOT NULL REFERENCES games (game_id),
+    sequence INTEGER NOT NULL,
+    is_start INTEGER NOT NULL,
+    player_id TEXT NOT NULL REFERENCES players (player_id),
+    team INTEGER NOT NULL,
+    batting_order INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    play_index INTEGER NOT NULL,
+    PRIMARY KEY (game_id, sequence)
+);
+
+CREATE TABLE IF NOT EXISTS plays (
+    game_id TEXT NOT NULL REFERENCES games (game_id),
+    play_index INTEGER NOT NULL,
+    inning INTEGER NOT NULL,
+    batting_team INTEGER NOT NULL,
+    batter_id TEXT NOT NULL,
+    pitcher_id TEXT,
+    outs INTEGER NOT NULL,
+    first_runner_id TEXT,
+    second_runner_id TEXT,
+    third_runner_id TEXT,
+    count TEXT,
+    event_text TEXT NOT NULL,
+    event_code INTEGER NOT NULL,
+    note TEXT,
+    runs INTEGER NOT NULL,
+    rbi INTEGER NOT NULL,
+    PRIMARY KEY (game_id, play_index)
+);
+
+CREATE TABLE IF NOT EXISTS pitches (
+    game_id TEXT NOT NULL,
+    play_index INTEGER NOT NULL,
+    sequence INTEGER NOT NULL,
+    pitch_type TEXT NOT NULL,
+    pitch_modifier TEXT,
+    PRIMARY KEY (game_id, play_index, sequence),
+    FOREIGN KEY (game_id, play_index) REFERENCES plays (game_id, play_index)
+);
+
+CREATE TABLE IF NOT EXISTS advances (
+    game_id TEXT NOT NULL,
+    play_index INTEGER NOT NULL,
+    runner_id TEXT NOT NULL,
+    from_base TEXT NOT NULL,
+    to_base TEXT NOT NULL,
+    is_out INTEGER NOT NULL,
+    is_run INTEGER NOT NULL,
+    is_rbi INTEGER NOT NULL,
+    responsible_pitcher_id TEXT,
+    PRIMARY KEY (game_id, play_index, from_base),
+    FOREIGN KEY (game_id, play_index) REFERENCES plays (game_id, play_index)
+);
+
+CREATE TABLE IF NOT EXISTS fielding_credits (
+    game_id TEXT NOT NULL,
+    play_index INTEGER NOT NULL,
+    sequence INTEGER NOT NULL,
+    fielder TEXT NOT NULL,
+    player_id TEXT,
+    credit_type TEXT NOT NULL CHECK (credit_type IN ('PO', 'A', 'E')),
+    PRIMARY KEY (game_id, play_index, sequence),
+    FOREIGN KEY (game_id, play_index) REFERENCES plays (game_id, play_index)
+);
+
+CREATE TABLE IF NOT EXISTS comments (
+    game_id TEXT NOT NULL REFERENCES games (game_id),
+    play_index INTEGER,
+    sequence INTEGER NOT NULL,
+    comment TEXT NOT NULL,
+    PRIMARY KEY (game_id, sequence)
+);
+
+CREATE TABLE IF NOT EXISTS earned_runs (
+    game_id TEXT NOT NULL REFERENCES games (game_id),
+    player_id TEXT NOT NULL,
+    earned_runs INTEGER NOT NULL,
+    PRIMARY KEY (game_id, player_id)
+);
+";
+
+/// Loads parsed games into a normalized SQLite schema. Comments that come before the first
+/// play have no `play_index`.
+pub struct SqliteLoader {
+    connection: Connection,
+}
+
+impl SqliteLoader {
+    /// Creates any tables that don't exist yet.
+    pub fn new(connection: Connection) -> Result<Self, String> {
+        connection.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+        Ok(Self { connection })
+    }
+
+    pub fn open(path: &str) -> Result<Self, String> {
+        Self::new(Connection::open(path).map_err(|e| e.to_string())?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::new(Connection::open_in_memory().map_err(|e| e.to_string())?)
+    }
+
+    pub fn get_connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Inserts the games in a single transaction, so either all of them are loaded or none.
+    pub fn load(&mut self, games: &[Game]) -> Result<(), String> {
+        let transaction = self.connection.transaction().map_err(|e| e.to_string())?;
+        for game in games {
+            insert_game(&transaction, game).map_err(|e| format!("{}: {e}", game.get_id()))?;
+        }
+        transaction.commit().map_err(|e| e.to_string())
+    }
+
+    /// Parses an event file and loads its games, returning how many there were.
+    pub fn load_file(&mut self, file_content: &str) -> Result<usize, String> {
+        let games = FileParser::new().parse_file(file_content)?;
+        self.load(&games)?;
+        Ok(games.len())
+    }
+}
+
+fn starting_base_code(base: &Base) -> String {
+    match base {
+        Base::Home => "B".to_string(),
+        base => base.to_string(),
+    }
+}
+
+fn insert_game(transaction: &Transaction, game: &Game) -> rusqlite::Result<()> {
+    let info = game.get_info_state();
+    let line_score = game.get_line_score();
+
+    let mut insert_game_row = transaction.prepare_cached("INSERT INTO games VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)")?;
+    insert_game_row.execute(params![
+        game.get_id(),
+        info.date,
+        info.number,
+        info.visiting_team,
+        info.home_team,
+        info.site,
+        info.start_time,
+        info.day_night.map(|day_night| day_night.to_string()),
+        info.innings,
+        info.used_designated_hitter_rule,
+        match info.temperature {
+            Some(TemperatureInfo::Known(temperature)) => Some(temperature),
+            _ => None,
+        },
+        match info.time_of_game {
+            Some(TimeOfGameInfo::Known(minutes)) => Some(minutes),
+            _ => None,
+        },
+        match info.attendance {
+            Some(AttendanceInfo::Known(attendance)) => Some(attendance),
+            _ => None,
+        },
+        info.wp,
+        info.lp,
+        info.save,
+        info.game_type.map(|game_type| game_type.to_string()),
+        line_score.get_runs(&Team::Visiting),
+        line_score.get_runs(&Team::Home),
+    ])?;
+
+    let mut insert_player = transaction.prepare_cached("INSERT OR IGNORE INTO players VALUES (?1, ?2)")?;
+    let mut insert_lineup = transaction.prepare_cached("INSERT INTO lineups VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)")?;
+    for (sequence, entry) in game.get_lineup_entries().iter().enumerate() {
+        insert_player.execute(params![entry.player_id, entry.player_name])?;
+        insert_lineup.execute(params![
+            game.get_id(),
+            sequence,
+            entry.is_start,
+            entry.player_id,
+            entry.team.to_string(),
+            entry.batting_order,
+            entry.position.to_string().parse::<u8>().unwrap_or_default(),
+            entry.play_index,
+        ])?;
+    }
+
+    let mut insert_comment = transaction.prepare_cached("INSERT INTO comments VALUES (?1, ?2, ?3, ?4)")?;
+    let mut comment_sequence = 0;
+    for comment in game.get_comments() {
+        insert_comment.execute(params![game.get_id(), None::<usize>, comment_sequence, comment])?;
+        comment_sequence += 1;
+    }
+
+    for (play_index, play) in game.get_plays().iter().enumerate() {
+        insert_play(transaction, game.get_id(), play_index, play)?;
+
+        for comment in play.get_comments() {
+            insert_comment.execute(params![game.get_id(), play_index, comment_sequence, comment])?;
+            comment_sequence += 1;
+        }
+    }
+
+    let mut insert_earned_runs = transaction.prepare_cached("INSERT INTO earned_runs VALUES (?1, ?2, ?3)")?;
+    for (player_id, earned_runs) in game.get_earned_runs() {
+        insert_earned_runs.execute(params![game.get_id(), player_id, earned_runs])?;
+    }
+
+    Ok(())
+}
+
+fn insert_play(transaction: &Transaction, game_id: &str, play_index: usize, play: &Play) -> rusqlite::Result<()> {
+    let runner = |base: Base| play.get_runner(&base).map(|runner| runner.id.clone());
+    let event_code = play.get_event().map(EventCategory::from_event).unwrap_or(EventCategory::Unknown);
+
+    let mut insert_play_row = transaction.prepare_cached("INSERT INTO plays VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)")?;
+    insert_play_row.execute(params![
+        game_id,
+        play_index,
+        play.get_inning(),
+        play.get_team().to_string(),
+        play.get_batter_id(),
+        play.get_pitcher_id(),
+        play.get_outs(),
+        runner(Base::First),
+        runner(Base::Second),
+        runner(Base::Third),
+        play.get_count().map(|count| count.to_string()),
+        play.get_event_text(),
+        event_code.code(),
+        play.get_note().map(|note| note.to_string()),
+        play.get_runs(),
+        play.get_rbi(),
+    ])?;
+
+    let mut insert_pitch = transaction.prepare_cached("INSERT INTO pitches VALUES (?1, ?2, ?3, ?4, ?5)")?;
+    for (sequence, pitch) in play.get_pitches().into_iter().flatten().enumerate() {
+        insert_pitch.execute(params![
+            game_id,
+            play_index,
+            sequence,
+            pitch.pitch_type.to_string(),
+            pitch.pitch_modifier.as_ref().map(|modifier| modifier.to_string()),
+        ])?;
+    }
+
+    let mut insert_advance = transaction.prepare_cached("INSERT INTO advances VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)")?;
+    for runner_advance in play.get_runner_advances() {
+        let advance = &runner_advance.advance;
+        insert_advance.execute(params![
+            game_id,
+            play_index,
+            runner_advance.runner.id,
+            starting_base_code(&advance.starting_base),
+            advance.ending_base.to_string(),
+            advance.is_out(),
+            advance.is_run(),
+            play.credits_rbi(advance),
+            runner_advance.runner.pitcher_id,
+        ])?;
+    }
+
+    let mut insert_fielding_credit = transaction.prepare_cached("INSERT INTO fielding_credits VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+    for (sequence, credit) in play.get_fielding_credits().iter().enumerate() {
+        let credit_type = match credit.credit_type {
+            FieldingCreditType::Putout => "PO",
+            FieldingCreditType::Assist => "A",
+            FieldingCreditType::Error => "E",
+        };
+
+        insert_fielding_credit.execute(params![game_id, play_index, sequence, credit.fielder.to_string(), credit.player_id, credit_type])?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn loader() -> SqliteLoader {
+        let mut loader = SqliteLoader::open_in_memory().unwrap();
+        assert_eq!(loader.load_file(fixtures::SQLITE_FILE), Ok(1));
+        loader
+    }
+
+    fn count(loader: &SqliteLoader, sql: &str) -> i64 {
+        loader.get_connection().query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn loads_every_table() {
+        let loader = loader();
+
+        assert_eq!(count(&loader, "SELECT home_score FROM games"), 1);
+        assert_eq!(count(&loader, "SELECT attendance FROM games"), 43504);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM players"), 8);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM lineups WHERE is_start = 0 AND play_index = 4"), 1);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM plays"), 6);
+        assert_eq!(count(&loader, "SELECT SUM(batting_team) FROM plays"), 2);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM pitches WHERE play_index = 2"), 6);
+        assert_eq!(count(&loader, "SELECT rbi FROM plays WHERE play_index = 2"), 1);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM advances WHERE is_run = 1"), 2);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM advances WHERE is_out = 1 AND play_index = 3"), 2);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM fielding_credits WHERE credit_type = 'PO'"), 3);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM comments WHERE play_index IS NULL"), 1);
+        assert_eq!(count(&loader, "SELECT play_index FROM comments WHERE comment LIKE 'Span%'"), 2);
+        assert_eq!(count(&loader, "SELECT event_code FROM plays WHERE play_index = 4"), 23);
+        assert_eq!(count(&loader, "SELECT earned_runs FROM earned_runs WHERE player_id = 'weavj003'"), 1);
+
+        let runner: String = loader
+            .get_connection()
+            .query_row("SELECT second_runner_id FROM plays WHERE play_index = 2", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(runner, "spand001");
+
+        let bases: (String, String) = loader
+            .get_connection()
+            .query_row("SELECT from_base, to_base FROM advances WHERE is_run = 1 AND play_index = 4", [], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(bases, ("B".to_string(), "H".to_string()));
+
+        let to_base: String = loader
+            .get_connection()
+            .query_row("SELECT to_base FROM advances WHERE runner_id = 'spand001' AND play_index = 2", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(to_base, "H");
+    }
+
+    #[test]
+    fn failed_load_rolls_back() {
+        let mut loader = loader();
+
+        // the same game again breaks the primary key, so none of it is kept
+        assert!(loader.load_file(fixtures::SQLITE_FILE).is_err());
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM games"), 1);
+        assert_eq!(count(&loader, "SELECT COUNT(*) FROM plays"), 6);
+    }
+}
@@ -0,0 +1,41 @@
+pub mod batted_ball;
+pub mod batting;
+pub mod fielding;
+pub mod leverage;
+pub mod pitching;
+pub mod run_expectancy;
+pub mod run_value;
+pub mod win_expectancy;
+
+use crate::parser::game::{EventType, Play};
+
+/// A rate, or nothing when there's nothing to divide by.
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    if denominator == 0.0 { None } else { Some(numerator / denominator) }
+}
+
+/// The players on offense credited with a play's value, each with an equal share. A play that
+/// ends a plate appearance goes to the batter and a stolen base, caught stealing or pickoff to
+/// the runners it names. Anything else, such as a wild pitch or balk, goes to nobody.
+fn credited_players(play: &Play) -> Vec<String> {
+    let Some(event) = play.get_event() else {
+        return Vec::new();
+    };
+
+    if event.event_type.ends_plate_appearance() {
+        return vec![play.get_batter_id().clone()];
+    }
+
+    let bases = match &event.event_type {
+        EventType::StolenBase { bases } => bases.iter().map(|base| base.previous()).collect(),
+        EventType::CaughtStealing { base, .. } | EventType::Pickoff { caught_stealing: true, base, .. } => vec![base.previous()],
+        EventType::Pickoff { caught_stealing: false, base, .. } => vec![*base],
+        _ => Vec::new(),
+    };
+
+    bases
+        .iter()
+        .filter_map(|base| play.get_runner(base))
+        .map(|runner| runner.id.clone())
+        .collect()
+}
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+use crate::parser::game::{BattedBall, FieldDepth, FieldSide, Game, Hand, Play, Trajectory};
+use crate::parser::roster::Roster;
+use crate::stats::ratio;
+
+/// Where and how balls in play were hit, over any span of plays. Each breakdown only counts
+/// the balls it could be told for.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BattedBallTotals {
+    pub batted_balls: u32,
+    /// Bunts are counted with the trajectory they had, as well as on their own.
+    pub ground_balls: u32,
+    pub line_drives: u32,
+    pub fly_balls: u32,
+    pub pop_ups: u32,
+    pub bunts: u32,
+    /// Needs the batter's hand.
+    pub pulled: u32,
+    pub center: u32,
+    pub opposite: u32,
+    pub infield: u32,
+    pub outfield: u32,
+    pub short: u32,
+    pub normal_depth: u32,
+    pub deep: u32,
+    pub extremely_deep: u32,
+}
+
+impl BattedBallTotals {
+    fn with_trajectory(&self) -> f64 {
+        (self.ground_balls + self.line_drives + self.fly_balls + self.pop_ups) as f64
+    }
+
+    fn with_direction(&self) -> f64 {
+        (self.pulled + self.center + self.opposite) as f64
+    }
+
+    fn with_depth(&self) -> f64 {
+        (self.short + self.normal_depth + self.deep + self.extremely_deep) as f64
+    }
+
+    pub fn get_ground_ball_rate(&self) -> Option<f64> {
+        ratio(self.ground_balls as f64, self.with_trajectory())
+    }
+
+    pub fn get_line_drive_rate(&self) -> Option<f64> {
+        ratio(self.line_drives as f64, self.with_trajectory())
+    }
+
+    pub fn get_fly_ball_rate(&self) -> Option<f64> {
+        ratio(self.fly_balls as f64, self.with_trajectory())
+    }
+
+    pub fn get_pop_up_rate(&self) -> Option<f64> {
+        ratio(self.pop_ups as f64, self.with_trajectory())
+    }
+
+    pub fn get_pull_rate(&self) -> Option<f64> {
+        ratio(self.pulled as f64, self.with_direction())
+    }
+
+    pub fn get_center_rate(&self) -> Option<f64> {
+        ratio(self.center as f64, self.with_direction())
+    }
+
+    pub fn get_opposite_field_rate(&self) -> Option<f64> {
+        ratio(self.opposite as f64, self.with_direction())
+    }
+
+    pub fn get_infield_rate(&self) -> Option<f64> {
+        ratio(self.infield as f64, (self.infield + self.outfield) as f64)
+    }
+
+    pub fn get_depth_rate(&self, depth: &FieldDepth) -> Option<f64> {
+        let count = match depth {
+            FieldDepth::Short => self.short,
+            FieldDepth::Normal => self.normal_depth,
+            FieldDepth::Deep => self.deep,
+            FieldDepth::ExtremelyDeep => self.extremely_deep,
+        };
+        ratio(count as f64, self.with_depth())
+    }
+}
+
+impl AddAssign<&BattedBallTotals> for BattedBallTotals {
+    fn add_assign(&mut self, other: &BattedBallTotals) {
+        self.batted_balls += other.batted_balls;
+        self.ground_balls += other.ground_balls;
+        self.line_drives += other.line_drives;
+        self.fly_balls += other.fly_balls;
+        self.pop_ups += other.pop_ups;
+        self.bunts += other.bunts;
+        self.pulled += other.pulled;
+        self.center += other.center;
+        self.opposite += other.opposite;
+        self.infield += other.infield;
+        self.outfield += other.outfield;
+        self.short += other.short;
+        self.normal_depth += other.normal_depth;
+        self.deep += other.deep;
+        self.extremely_deep += other.extremely_deep;
+    }
+}
+
+/// Batted-ball profiles for every batter and pitcher. A ball in play is any plate appearance
+/// with a trajectory or a location. Pulled balls go to the batter's side of the field, so
+/// they're only told for batters in the roster, with switch hitters needing the pitcher too.
+#[derive(Clone, Debug, Default)]
+pub struct BattedBallStats {
+    roster: Roster,
+    batters: HashMap<String, BattedBallTotals>,
+    pitchers: HashMap<String, BattedBallTotals>,
+}
+
+impl BattedBallStats {
+    pub fn new() -> Self {
+        Self::with_roster(Roster::new())
+    }
+
+    pub fn with_roster(roster: Roster) -> Self {
+        Self {
+            roster,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_games(&mut self, games: &[Game]) {
+        games.iter().for_each(|game| self.add_game(game));
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        for play in game.get_plays() {
+            let Some(totals) = self.play_totals(play) else {
+                continue;
+            };
+
+            *self.batters.entry(play.get_batter_id().clone()).or_default() += &totals;
+            if let Some(pitcher_id) = play.get_pitcher_id() {
+                *self.pitchers.entry(pitcher_id.clone()).or_default() += &totals;
+            }
+        }
+    }
+
+    pub fn get_batter(&self, player_id: &str) -> Option<&BattedBallTotals> {
+        self.batters.get(player_id)
+    }
+
+    pub fn get_pitcher(&self, player_id: &str) -> Option<&BattedBallTotals> {
+        self.pitchers.get(player_id)
+    }
+
+    pub fn get_batters(&self) -> &HashMap<String, BattedBallTotals> {
+        &self.batters
+    }
+
+    pub fn get_pitchers(&self) -> &HashMap<String, BattedBallTotals> {
+        &self.pitchers
+    }
+
+    fn play_totals(&self, play: &Play) -> Option<BattedBallTotals> {
+        let event = play.get_event().filter(|event| event.event_type.ends_plate_appearance())?;
+        let BattedBall { trajectory, location } = event.get_batted_ball()?;
+
+        let mut totals = BattedBallTotals {
+            batted_balls: 1,
+            ..Default::default()
+        };
+
+        match trajectory {
+            Some(Trajectory::GroundBall | Trajectory::GroundBallBunt) => totals.ground_balls += 1,
+            Some(Trajectory::LineDrive | Trajectory::LineDriveBunt) => totals.line_drives += 1,
+            Some(Trajectory::Fly) => totals.fly_balls += 1,
+            Some(Trajectory::PopFly | Trajectory::PopUpBunt) => totals.pop_ups += 1,
+            None => {},
+        }
+        totals.bunts += trajectory.is_some_and(|trajectory| trajectory.is_bunt()) as u32;
+
+        let Some(location) = location else {
+            return Some(totals);
+        };
+
+        let batting_hand = self.roster.get_batting_hand(play.get_batter_id(), play.get_pitcher_id().map(String::as_str));
+        match (location.get_side(), batting_hand) {
+            (FieldSide::Center, _) => totals.center += 1,
+            (FieldSide::Left, Some(Hand::Right)) | (FieldSide::Right, Some(Hand::Left)) => totals.pulled += 1,
+            (FieldSide::Left, Some(Hand::Left)) | (FieldSide::Right, Some(Hand::Right)) => totals.opposite += 1,
+            _ => {},
+        }
+
+        if location.is_outfield() {
+            totals.outfield += 1;
+        } else {
+            totals.infield += 1;
+        }
+
+        match location.get_depth() {
+            FieldDepth::Short => totals.short += 1,
+            FieldDepth::Normal => totals.normal_depth += 1,
+            FieldDepth::Deep => totals.deep += 1,
+            FieldDepth::ExtremelyDeep => totals.extremely_deep += 1,
+        }
+
+        Some(totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    const ROSTER: &str = "spand001,Span,Denard,L,L,MIN,OF
+hudso001,Hudson,Orlando,B,R,MIN,2B
+weavj003,Weaver,Jered,R,R,ANA,P
+";
+
+    fn stats() -> BattedBallStats {
+        let mut roster = Roster::new();
+        roster.parse_file(ROSTER).unwrap();
+
+        let mut stats = BattedBallStats::with_roster(roster);
+        stats.add_games(&fixtures::parse(fixtures::BATTED_BALL_FILE));
+        stats
+    }
+
+    #[test]
+    fn batter_profiles() {
+        let stats = stats();
+
+        // a lefty pulls to right and a switch hitter facing a righty bats left
+        let span = stats.get_batter("spand001").unwrap();
+        assert_eq!((span.batted_balls, span.line_drives, span.pop_ups, span.opposite, span.pulled), (2, 1, 1, 1, 1));
+        assert_eq!((span.short, span.normal_depth, span.outfield, span.infield), (1, 1, 1, 1));
+
+        let hudson = stats.get_batter("hudso001").unwrap();
+        assert_eq!((hudson.batted_balls, hudson.ground_balls, hudson.bunts, hudson.fly_balls), (3, 2, 1, 1));
+        assert_eq!((hudson.pulled, hudson.opposite), (1, 2));
+        assert_eq!(hudson.get_ground_ball_rate(), Some(2.0 / 3.0));
+        assert_eq!(hudson.get_depth_rate(&FieldDepth::Deep), Some(1.0 / 3.0));
+
+        // Mauer isn't in the roster, so his side is only known when it's center
+        let mauer = stats.get_batter("mauej001").unwrap();
+        assert_eq!((mauer.batted_balls, mauer.center, mauer.extremely_deep, mauer.outfield), (2, 1, 1, 1));
+        assert_eq!(mauer.get_center_rate(), Some(1.0));
+        assert_eq!(mauer.get_infield_rate(), Some(0.0));
+
+        // the trajectory and location written together as G5
+        let cuddyer = stats.get_batter("cuddm001").unwrap();
+        assert_eq!((cuddyer.ground_balls, cuddyer.infield, cuddyer.normal_depth), (1, 1, 1));
+        assert_eq!(cuddyer.get_pull_rate(), None);
+    }
+
+    #[test]
+    fn pitcher_profile() {
+        let stats = stats();
+
+        let weaver = stats.get_pitcher("weavj003").unwrap();
+        assert_eq!(weaver.batted_balls, 8);
+        assert_eq!(weaver.get_fly_ball_rate(), Some(2.0 / 8.0));
+        assert_eq!(weaver.get_pull_rate(), Some(2.0 / 6.0));
+        assert_eq!(weaver.get_line_drive_rate(), Some(1.0 / 8.0));
+        assert_eq!(BattedBallTotals::default().get_pop_up_rate(), None);
+        assert!(stats.get_batter("abreb001").is_none());
+    }
+}
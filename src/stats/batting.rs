@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+use crate::parser::game::{EventModifier, EventType, Game, Play};
+use crate::stats::ratio;
+
+/// Counting stats for a batter over any span of plays.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BattingTotals {
+    pub plate_appearances: u32,
+    pub at_bats: u32,
+    pub runs: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub home_runs: u32,
+    pub rbi: u32,
+    /// Including intentional walks.
+    pub walks: u32,
+    pub intentional_walks: u32,
+    pub strikeouts: u32,
+    pub hit_by_pitch: u32,
+    pub sacrifice_hits: u32,
+    pub sacrifice_flies: u32,
+    pub grounded_into_double_plays: u32,
+    pub stolen_bases: u32,
+    pub caught_stealing: u32,
+}
+
+impl BattingTotals {
+    pub fn get_singles(&self) -> u32 {
+        self.hits - self.doubles - self.triples - self.home_runs
+    }
+
+    pub fn get_total_bases(&self) -> u32 {
+        self.hits + self.doubles + 2 * self.triples + 3 * self.home_runs
+    }
+
+    pub fn get_batting_average(&self) -> Option<f64> {
+        ratio(self.hits as f64, self.at_bats as f64)
+    }
+
+    /// (H + BB + HBP) / (AB + BB + HBP + SF), with intentional walks counted as walks.
+    pub fn get_on_base_percentage(&self) -> Option<f64> {
+        let times_on_base = self.hits + self.walks + self.hit_by_pitch;
+        ratio(times_on_base as f64, (self.at_bats + self.walks + self.hit_by_pitch + self.sacrifice_flies) as f64)
+    }
+
+    pub fn get_slugging_percentage(&self) -> Option<f64> {
+        ratio(self.get_total_bases() as f64, self.at_bats as f64)
+    }
+
+    pub fn get_on_base_plus_slugging(&self) -> Option<f64> {
+        Some(self.get_on_base_percentage()? + self.get_slugging_percentage()?)
+    }
+}
+
+impl AddAssign<&BattingTotals> for BattingTotals {
+    fn add_assign(&mut self, other: &BattingTotals) {
+        self.plate_appearances += other.plate_appearances;
+        self.at_bats += other.at_bats;
+        self.runs += other.runs;
+        self.hits += other.hits;
+        self.doubles += other.doubles;
+        self.triples += other.triples;
+        self.home_runs += other.home_runs;
+        self.rbi += other.rbi;
+        self.walks += other.walks;
+        self.intentional_walks += other.intentional_walks;
+        self.strikeouts += other.strikeouts;
+        self.hit_by_pitch += other.hit_by_pitch;
+        self.sacrifice_hits += other.sacrifice_hits;
+        self.sacrifice_flies += other.sacrifice_flies;
+        self.grounded_into_double_plays += other.grounded_into_double_plays;
+        self.stolen_bases += other.stolen_bases;
+        self.caught_stealing += other.caught_stealing;
+    }
+}
+
+/// Batting totals by player for every game and season added. A season is the year the game
+/// was played, or 0 if that can't be told.
+#[derive(Clone, Debug, Default)]
+pub struct BattingStats {
+    games: HashMap<String, HashMap<String, BattingTotals>>,
+    seasons: HashMap<u16, HashMap<String, BattingTotals>>,
+}
+
+impl BattingStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game]) -> Self {
+        let mut stats = Self::new();
+        games.iter().for_each(|game| stats.add_game(game));
+        stats
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        let season = game.get_season().unwrap_or(0);
+        for play in game.get_plays() {
+            self.add_play(game.get_id(), season, play);
+        }
+    }
+
+    pub fn add_play(&mut self, game_id: &str, season: u16, play: &Play) {
+        for (player_id, totals) in play_totals(play) {
+            *self.games.entry(game_id.to_string()).or_default().entry(player_id.clone()).or_default() += &totals;
+            *self.seasons.entry(season).or_default().entry(player_id).or_default() += &totals;
+        }
+    }
+
+    pub fn get_game(&self, game_id: &str, player_id: &str) -> Option<&BattingTotals> {
+        self.games.get(game_id)?.get(player_id)
+    }
+
+    /// Every player who batted or ran in the game.
+    pub fn get_game_totals(&self, game_id: &str) -> Option<&HashMap<String, BattingTotals>> {
+        self.games.get(game_id)
+    }
+
+    pub fn get_season(&self, season: u16, player_id: &str) -> Option<&BattingTotals> {
+        self.seasons.get(&season)?.get(player_id)
+    }
+
+    pub fn get_season_totals(&self, season: u16) -> Option<&HashMap<String, BattingTotals>> {
+        self.seasons.get(&season)
+    }
+
+    /// The player's totals across every season added.
+    pub fn get_player(&self, player_id: &str) -> BattingTotals {
+        let mut totals = BattingTotals::default();
+        for season in self.seasons.values() {
+            if let Some(season_totals) = season.get(player_id) {
+                totals += season_totals;
+            }
+        }
+        totals
+    }
+}
+
+/// What the play adds for each batter and runner involved.
+fn play_totals(play: &Play) -> HashMap<String, BattingTotals> {
+    let mut totals = HashMap::<String, BattingTotals>::new();
+    let Some(event) = play.get_event() else {
+        return totals;
+    };
+
+    for runner_advance in play.get_runner_advances().iter().filter(|ra| ra.advance.is_run()) {
+        totals.entry(runner_advance.runner.id.clone()).or_default().runs += 1;
+    }
+
+    for event_type in event.event_type.with_base_running_event() {
+        if let EventType::StolenBase { bases } = event_type {
+            for base in bases {
+                if let Some(runner) = play.get_runner(&base.previous()) {
+                    totals.entry(runner.id.clone()).or_default().stolen_bases += 1;
+                }
+            }
+        }
+
+        let caught_stealing = match event_type {
+            EventType::CaughtStealing { base, .. } | EventType::Pickoff { caught_stealing: true, base, .. } => Some(base.previous()),
+            _ => None,
+        };
+
+        if let Some(runner) = caught_stealing.and_then(|base| play.get_runner(&base)) {
+            totals.entry(runner.id.clone()).or_default().caught_stealing += 1;
+        }
+    }
+
+    if !event.event_type.ends_plate_appearance() {
+        return totals;
+    }
+
+    let batter = totals.entry(play.get_batter_id().to_string()).or_default();
+    let total_bases = event.event_type.total_bases();
+    let has_modifier = |modifiers: &[EventModifier]| event.modifiers.iter().any(|modifier| modifiers.contains(modifier));
+
+    batter.plate_appearances += 1;
+    batter.at_bats += event.is_at_bat() as u32;
+    batter.hits += (total_bases > 0) as u32;
+    batter.doubles += (total_bases == 2) as u32;
+    batter.triples += (total_bases == 3) as u32;
+    batter.home_runs += (total_bases == 4) as u32;
+    batter.rbi += play.get_rbi() as u32;
+    batter.walks += matches!(event.event_type, EventType::Walk { .. }) as u32;
+    batter.intentional_walks += matches!(event.event_type, EventType::Walk { intentional: true, .. }) as u32;
+    batter.strikeouts += matches!(event.event_type, EventType::Strikeout { .. }) as u32;
+    batter.hit_by_pitch += matches!(event.event_type, EventType::HitByPitch) as u32;
+    batter.sacrifice_hits += has_modifier(&[EventModifier::SacrificeHitOrBunt]) as u32;
+    batter.sacrifice_flies += has_modifier(&[EventModifier::SacrificeFly]) as u32;
+    batter.grounded_into_double_plays += has_modifier(&[EventModifier::GroundBallDoublePlay, EventModifier::BuntGroundedIntoDoublePlay]) as u32;
+
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::box_score::BoxScore;
+    use crate::fixtures;
+    use crate::parser::game::Team;
+    use crate::stats::fielding::FieldingStats;
+
+    fn stats() -> BattingStats {
+        BattingStats::from_games(&fixtures::parse(fixtures::BATTING_FILE))
+    }
+
+    #[test]
+    fn game_totals() {
+        let stats = stats();
+
+        let hudson = stats.get_game("ANA201004050", "hudso001").unwrap();
+        assert_eq!((hudson.plate_appearances, hudson.at_bats, hudson.hits, hudson.rbi), (1, 1, 1, 1));
+
+        let span = stats.get_game("ANA201004050", "spand001").unwrap();
+        assert_eq!((span.runs, span.stolen_bases), (1, 1));
+
+        let mauer = stats.get_game("ANA201004050", "mauej001").unwrap();
+        assert_eq!(mauer.grounded_into_double_plays, 1);
+
+        let napoli = stats.get_game("ANA201004050", "napom001").unwrap();
+        assert_eq!((napoli.walks, napoli.intentional_walks, napoli.at_bats, napoli.plate_appearances), (1, 1, 0, 1));
+
+        let aybar = stats.get_game("ANA201004050", "aybae001").unwrap();
+        assert_eq!((aybar.hit_by_pitch, aybar.at_bats), (1, 0));
+
+        let kendrick = stats.get_game("ANA201004050", "kendh001").unwrap();
+        assert_eq!((kendrick.sacrifice_flies, kendrick.at_bats, kendrick.rbi), (1, 0, 1));
+    }
+
+    #[test]
+    fn season_totals_and_rates() {
+        let stats = stats();
+
+        let span = stats.get_season(2010, "spand001").unwrap();
+        assert_eq!((span.at_bats, span.hits, span.triples, span.runs), (2, 2, 1, 2));
+        assert_eq!(span.get_total_bases(), 4);
+        assert_eq!(span.get_slugging_percentage(), Some(2.0));
+
+        let mauer = stats.get_season(2010, "mauej001").unwrap();
+        assert_eq!((mauer.sacrifice_hits, mauer.at_bats, mauer.rbi), (1, 1, 1));
+        assert_eq!(mauer.get_batting_average(), Some(0.0));
+
+        let abreu = stats.get_player("abreb001");
+        assert_eq!((abreu.walks, abreu.caught_stealing, abreu.doubles), (1, 1, 1));
+        assert_eq!(abreu.get_on_base_percentage(), Some(1.0));
+        assert_eq!(abreu.get_on_base_plus_slugging(), Some(3.0));
+
+        assert_eq!(BattingTotals::default().get_batting_average(), None);
+        assert!(stats.get_season(2009, "spand001").is_none());
+    }
+
+    #[test]
+    fn matches_box_score_lines() {
+        let stats = BattingStats::from_games(&fixtures::games());
+
+        for (player_id, line) in fixtures::box_lines("bline") {
+            let totals = stats.get_game("ANA201004050", &player_id).unwrap();
+            let counts = [
+                totals.at_bats,
+                totals.runs,
+                totals.hits,
+                totals.doubles,
+                totals.triples,
+                totals.home_runs,
+                totals.rbi,
+                totals.sacrifice_hits,
+                totals.sacrifice_flies,
+                totals.hit_by_pitch,
+                totals.walks,
+                totals.intentional_walks,
+                totals.strikeouts,
+                totals.stolen_bases,
+                totals.caught_stealing,
+                totals.grounded_into_double_plays,
+            ];
+            // after the side, batting order and sequence, up to catcher's interference
+            assert_eq!(counts.as_slice(), &line[3..19], "{player_id}");
+        }
+    }
+
+    #[test]
+    fn box_score_proof() {
+        let games = fixtures::games();
+        let game = &games[0];
+        let batting = BattingStats::from_games(&games).get_game_totals(game.get_id()).unwrap().clone();
+        let fielding = FieldingStats::from_games(&games).get_game_totals(game.get_id()).unwrap().clone();
+        let box_score = BoxScore::from_game(game);
+
+        // every plate appearance ends with the batter scoring, left on base or put out
+        for team in [Team::Visiting, Team::Home] {
+            let batters = batting.iter().filter(|(player_id, _)| fixtures::plays_for(game, &team, player_id));
+            let (plate_appearances, runs) = batters.fold((0, 0), |(plate_appearances, runs), (_, totals)| {
+                (plate_appearances + totals.plate_appearances, runs + totals.runs)
+            });
+            let putouts: u32 = fielding
+                .iter()
+                .filter(|((player_id, _), _)| fixtures::plays_for(game, &team.opponent(), player_id))
+                .map(|(_, totals)| totals.putouts)
+                .sum();
+
+            let left_on_base = box_score.get_team(&team).left_on_base as u32;
+            assert_eq!(plate_appearances, runs + left_on_base + putouts, "{team:?}");
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::AddAssign;
+
+use crate::parser::game::{EventType, FieldingCreditType, Game, Play, Position};
+use crate::stats::ratio;
+
+/// Counting stats for a fielder at one position over any span of plays.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldingTotals {
+    pub games: u32,
+    /// Outs recorded while the player was at the position.
+    pub outs_played: u32,
+    pub putouts: u32,
+    pub assists: u32,
+    pub errors: u32,
+    pub double_plays: u32,
+    pub triple_plays: u32,
+    /// Catchers only, as are stolen bases allowed and caught stealing.
+    pub passed_balls: u32,
+    pub stolen_bases_allowed: u32,
+    pub caught_stealing: u32,
+}
+
+impl FieldingTotals {
+    /// Innings played as usually written, where `6.1` is six and a third.
+    pub fn get_innings_played(&self) -> String {
+        format!("{}.{}", self.outs_played / 3, self.outs_played % 3)
+    }
+
+    pub fn get_chances(&self) -> u32 {
+        self.putouts + self.assists + self.errors
+    }
+
+    pub fn get_fielding_percentage(&self) -> Option<f64> {
+        ratio((self.putouts + self.assists) as f64, self.get_chances() as f64)
+    }
+}
+
+impl AddAssign<&FieldingTotals> for FieldingTotals {
+    fn add_assign(&mut self, other: &FieldingTotals) {
+        self.games += other.games;
+        self.outs_played += other.outs_played;
+        self.putouts += other.putouts;
+        self.assists += other.assists;
+        self.errors += other.errors;
+        self.double_plays += other.double_plays;
+        self.triple_plays += other.triple_plays;
+        self.passed_balls += other.passed_balls;
+        self.stolen_bases_allowed += other.stolen_bases_allowed;
+        self.caught_stealing += other.caught_stealing;
+    }
+}
+
+/// Fielding totals by player and position for every game and season added. The designated
+/// hitter isn't a fielding position and is left out.
+#[derive(Clone, Debug, Default)]
+pub struct FieldingStats {
+    games: HashMap<String, HashMap<(String, Position), FieldingTotals>>,
+    seasons: HashMap<u16, HashMap<(String, Position), FieldingTotals>>,
+}
+
+impl FieldingStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game]) -> Self {
+        let mut stats = Self::new();
+        games.iter().for_each(|game| stats.add_game(game));
+        stats
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        let mut totals = HashMap::<(String, Position), FieldingTotals>::new();
+        let mut appearances = HashSet::<(String, Position)>::new();
+
+        for play in game.get_plays() {
+            for (position, player_id) in play.get_defense().iter().filter(|(position, _)| **position != Position::DesignatedHitter) {
+                appearances.insert((player_id.clone(), *position));
+            }
+            for (key, play_totals) in play_totals(play) {
+                *totals.entry(key).or_default() += &play_totals;
+            }
+        }
+
+        for key in appearances {
+            totals.entry(key).or_default().games += 1;
+        }
+
+        let season = game.get_season().unwrap_or(0);
+        for (key, player_totals) in &totals {
+            *self.seasons.entry(season).or_default().entry(key.clone()).or_default() += player_totals;
+        }
+        self.games.insert(game.get_id().to_string(), totals);
+    }
+
+    pub fn get_game(&self, game_id: &str, player_id: &str, position: &Position) -> Option<&FieldingTotals> {
+        self.games.get(game_id)?.get(&(player_id.to_string(), *position))
+    }
+
+    pub fn get_game_totals(&self, game_id: &str) -> Option<&HashMap<(String, Position), FieldingTotals>> {
+        self.games.get(game_id)
+    }
+
+    pub fn get_season(&self, season: u16, player_id: &str, position: &Position) -> Option<&FieldingTotals> {
+        self.seasons.get(&season)?.get(&(player_id.to_string(), *position))
+    }
+
+    pub fn get_season_totals(&self, season: u16) -> Option<&HashMap<(String, Position), FieldingTotals>> {
+        self.seasons.get(&season)
+    }
+
+    /// The player's totals at each position they played, across every season added.
+    pub fn get_player(&self, player_id: &str) -> HashMap<Position, FieldingTotals> {
+        let mut totals = HashMap::<Position, FieldingTotals>::new();
+        for season in self.seasons.values() {
+            for ((id, position), season_totals) in season {
+                if id == player_id {
+                    *totals.entry(*position).or_default() += season_totals;
+                }
+            }
+        }
+        totals
+    }
+}
+
+/// What the play adds for each fielder involved, keyed by player and position.
+fn play_totals(play: &Play) -> HashMap<(String, Position), FieldingTotals> {
+    let mut totals = HashMap::<(String, Position), FieldingTotals>::new();
+    let Some(event) = play.get_event() else {
+        return totals;
+    };
+
+    let defense = play.get_defense();
+    let outs = play.get_runner_advances().iter().filter(|ra| ra.advance.out).count() as u32;
+
+    for (position, player_id) in defense.iter().filter(|(position, _)| **position != Position::DesignatedHitter) {
+        totals.entry((player_id.clone(), *position)).or_default().outs_played += outs;
+    }
+
+    // everyone with a putout or assist takes part in a double or triple play
+    let mut participants = HashSet::<(String, Position)>::new();
+
+    for credit in play.get_fielding_credits() {
+        let (Some(player_id), Some(position)) = (credit.player_id, Position::from_fielder(&credit.fielder)) else {
+            continue;
+        };
+
+        let line = totals.entry((player_id.clone(), position)).or_default();
+        match credit.credit_type {
+            FieldingCreditType::Putout => line.putouts += 1,
+            FieldingCreditType::Assist => line.assists += 1,
+            FieldingCreditType::Error => {
+                line.errors += 1;
+                continue;
+            },
+        }
+        participants.insert((player_id, position));
+    }
+
+    for key in participants {
+        let line = totals.entry(key).or_default();
+        line.double_plays += event.is_double_play() as u32;
+        line.triple_plays += event.is_triple_play() as u32;
+    }
+
+    let Some(catcher_id) = defense.get(&Position::Catcher) else {
+        return totals;
+    };
+
+    let catcher = totals.entry((catcher_id.clone(), Position::Catcher)).or_default();
+    for event_type in event.event_type.with_base_running_event() {
+        match event_type {
+            EventType::PassedBall => catcher.passed_balls += 1,
+            EventType::StolenBase { bases } => catcher.stolen_bases_allowed += bases.len() as u32,
+            EventType::CaughtStealing { .. } | EventType::Pickoff { caught_stealing: true, .. } => catcher.caught_stealing += 1,
+            _ => {},
+        }
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::box_score::BoxScore;
+    use crate::fixtures;
+    use crate::parser::game::Team;
+    use crate::stats::pitching::PitchingStats;
+
+    fn stats() -> FieldingStats {
+        FieldingStats::from_games(&fixtures::parse(fixtures::FIELDING_FILE))
+    }
+
+    #[test]
+    fn credits_by_position() {
+        let stats = stats();
+        let get = |player_id, position| stats.get_game("ANA201004050", player_id, &position).unwrap();
+
+        let aybar = get("aybae001", Position::Shortstop);
+        assert_eq!((aybar.putouts, aybar.assists, aybar.errors, aybar.double_plays), (0, 2, 1, 1));
+        assert_eq!(aybar.get_chances(), 3);
+
+        let kendrick = get("kendh001", Position::SecondBase);
+        assert_eq!((kendrick.putouts, kendrick.double_plays, kendrick.outs_played), (2, 1, 3));
+        assert_eq!(kendrick.get_fielding_percentage(), Some(1.0));
+        assert_eq!(FieldingTotals::default().get_fielding_percentage(), None);
+
+        let napoli = get("napom001", Position::Catcher);
+        assert_eq!((napoli.passed_balls, napoli.stolen_bases_allowed, napoli.caught_stealing, napoli.assists), (1, 1, 1, 1));
+        assert_eq!((napoli.putouts, napoli.get_innings_played().as_str()), (1, "2.0"));
+    }
+
+    #[test]
+    fn innings_follow_substitutions() {
+        let stats = stats();
+
+        let kendrick = stats.get_player("kendh001");
+        assert_eq!(kendrick.len(), 2);
+        assert_eq!(kendrick[&Position::FirstBase].outs_played, 3);
+        assert_eq!(kendrick[&Position::FirstBase].putouts, 2);
+        assert_eq!(kendrick[&Position::SecondBase].games, 1);
+
+        let morales = stats.get_season(2010, "morak001", &Position::FirstBase).unwrap();
+        assert_eq!((morales.outs_played, morales.games, morales.putouts), (3, 1, 1));
+
+        assert_eq!(stats.get_season(2010, "rodrs001", &Position::SecondBase).unwrap().assists, 1);
+        assert!(stats.get_season(2010, "weavj003", &Position::DesignatedHitter).is_none());
+    }
+
+    #[test]
+    fn matches_box_score_lines() {
+        let stats = FieldingStats::from_games(&fixtures::games());
+
+        for (player_id, line) in fixtures::box_lines("dline") {
+            let position = line[2].to_string().parse().unwrap();
+            let totals = stats.get_game("ANA201004050", &player_id, &position).unwrap();
+            let counts = [
+                totals.outs_played,
+                totals.putouts,
+                totals.assists,
+                totals.errors,
+                totals.double_plays,
+                totals.triple_plays,
+                totals.passed_balls,
+            ];
+            // after the side, sequence and position
+            assert_eq!(counts.as_slice(), &line[3..], "{player_id}");
+        }
+    }
+
+    #[test]
+    fn box_score_proof() {
+        let games = fixtures::games();
+        let game = &games[0];
+        let fielding = FieldingStats::from_games(&games).get_game_totals(game.get_id()).unwrap().clone();
+        let pitching = PitchingStats::from_games(&games).get_game_totals(game.get_id()).unwrap().clone();
+        let box_score = BoxScore::from_game(game);
+
+        // every out has a putout, and each position is filled for every out
+        for team in [Team::Visiting, Team::Home] {
+            let outs: u32 = pitching
+                .iter()
+                .filter(|(player_id, _)| fixtures::plays_for(game, &team, player_id))
+                .map(|(_, totals)| totals.outs)
+                .sum();
+            let fielders = fielding
+                .iter()
+                .filter(|((player_id, _), _)| fixtures::plays_for(game, &team, player_id))
+                .collect::<Vec<_>>();
+
+            let putouts: u32 = fielders.iter().map(|(_, totals)| totals.putouts).sum();
+            let errors: u32 = fielders.iter().map(|(_, totals)| totals.errors).sum();
+            assert_eq!(putouts, outs, "{team:?}");
+            assert_eq!(errors, box_score.get_team(&team).errors as u32, "{team:?}");
+
+            for position in [
+                Position::Pitcher,
+                Position::Catcher,
+                Position::FirstBase,
+                Position::SecondBase,
+                Position::ThirdBase,
+                Position::Shortstop,
+                Position::LeftField,
+                Position::CenterField,
+                Position::RightField,
+            ] {
+                let outs_played: u32 = fielders
+                    .iter()
+                    .filter(|((_, fielder_position), _)| *fielder_position == position)
+                    .map(|(_, totals)| totals.outs_played)
+                    .sum();
+                assert_eq!(outs_played, outs, "{team:?} {position:?}");
+            }
+        }
+    }
+}
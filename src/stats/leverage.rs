@@ -0,0 +1,312 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::game::{Game, Position, Team};
+use crate::stats::ratio;
+use crate::stats::win_expectancy::{WinExpectancy, WinExpectancyState};
+
+/// A plate appearance and how much was riding on it when it began.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlateAppearanceLeverage {
+    /// The index of the play that ended the plate appearance.
+    pub play_index: usize,
+    pub state: WinExpectancyState,
+    pub batter_id: String,
+    pub pitcher_id: Option<String>,
+    pub leverage_index: Option<f64>,
+    /// Over every play of the plate appearance, for the batting team.
+    pub win_probability_added: Option<f64>,
+}
+
+/// The leverage index of each state: the average swing in win probability from plays starting
+/// there, relative to the average swing of every play. A state of average importance is 1.
+#[derive(Clone, Debug, Default)]
+pub struct LeverageIndex {
+    win_expectancy: WinExpectancy,
+    /// The total swing and the number of plays, for each state and for every play.
+    swings: HashMap<WinExpectancyState, (f64, u32)>,
+    total_swing: (f64, u32),
+}
+
+impl LeverageIndex {
+    pub fn new(win_expectancy: WinExpectancy) -> Self {
+        Self {
+            win_expectancy,
+            ..Default::default()
+        }
+    }
+
+    /// Builds the index from the games' plays, measured with the given win expectancy.
+    pub fn from_games(games: &[Game], win_expectancy: WinExpectancy) -> Self {
+        let mut leverage_index = Self::new(win_expectancy);
+        games.iter().for_each(|game| leverage_index.add_game(game));
+        leverage_index
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        for play in self.win_expectancy.plays(game) {
+            let (Some(before), Some(after)) = (play.home_win_probability_before, play.home_win_probability_after) else {
+                continue;
+            };
+
+            let swing = (after - before).abs();
+            let (state_swing, plays) = self.swings.entry(play.state).or_default();
+            *state_swing += swing;
+            *plays += 1;
+            self.total_swing.0 += swing;
+            self.total_swing.1 += 1;
+        }
+    }
+
+    pub fn get_win_expectancy(&self) -> &WinExpectancy {
+        &self.win_expectancy
+    }
+
+    /// The state's leverage index, or nothing if no play from it had a known outcome.
+    pub fn get(&self, state: &WinExpectancyState) -> Option<f64> {
+        let (state_swing, plays) = self.swings.get(state)?;
+        let average = ratio(self.total_swing.0, self.total_swing.1 as f64)?;
+        ratio(ratio(*state_swing, *plays as f64)?, average)
+    }
+
+    /// Every plate appearance in the game, with the leverage of the state it began in. A
+    /// plate appearance carried over into a new half-inning starts again there.
+    pub fn plate_appearances(&self, game: &Game) -> Vec<PlateAppearanceLeverage> {
+        let mut plate_appearances = Vec::new();
+        let mut start: Option<(WinExpectancyState, Option<f64>)> = None;
+
+        for play_win_probability in self.win_expectancy.plays(game) {
+            let play = &game.get_plays()[play_win_probability.play_index];
+            let state = play_win_probability.state;
+
+            let (start_state, win_probability_added) = match start {
+                Some((start_state, added)) if start_state.inning == state.inning && start_state.team == state.team => {
+                    (start_state, added.zip(play_win_probability.get_win_probability_added()).map(|(a, b)| a + b))
+                },
+                _ => (state, play_win_probability.get_win_probability_added()),
+            };
+            start = Some((start_state, win_probability_added));
+
+            if play.get_event().is_some_and(|event| event.event_type.ends_plate_appearance()) {
+                plate_appearances.push(PlateAppearanceLeverage {
+                    play_index: play_win_probability.play_index,
+                    state: start_state,
+                    batter_id: play_win_probability.batter_id,
+                    pitcher_id: play_win_probability.pitcher_id,
+                    leverage_index: self.get(&start_state),
+                    win_probability_added,
+                });
+                start = None;
+            }
+        }
+
+        plate_appearances
+    }
+}
+
+/// Leverage for a batter or pitcher over any span of plate appearances. Only plate appearances
+/// with a known leverage index count.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LeverageTotals {
+    pub plate_appearances: u32,
+    pub leverage: f64,
+    /// For the batter's team, or against the pitcher's.
+    pub win_probability_added: f64,
+    /// Win probability added with each plate appearance divided by its leverage.
+    pub context_neutral_wins: f64,
+    /// Pitchers only: games they came into in relief, and the leverage of the first plate
+    /// appearance in each. Appearances whose first plate appearance has no known leverage
+    /// aren't counted.
+    pub relief_appearances: u32,
+    pub entering_leverage: f64,
+}
+
+impl LeverageTotals {
+    /// pLI: the average leverage of the player's plate appearances.
+    pub fn get_average_leverage(&self) -> Option<f64> {
+        ratio(self.leverage, self.plate_appearances as f64)
+    }
+
+    /// gmLI: the average leverage when the pitcher came into the game.
+    pub fn get_game_entry_leverage(&self) -> Option<f64> {
+        ratio(self.entering_leverage, self.relief_appearances as f64)
+    }
+
+    /// How much better the player did in high leverage than otherwise, as WPA / pLI less
+    /// WPA / LI.
+    pub fn get_clutch(&self) -> Option<f64> {
+        Some(ratio(self.win_probability_added, self.get_average_leverage()?)? - self.context_neutral_wins)
+    }
+}
+
+/// Leverage totals by player across any set of games.
+#[derive(Clone, Debug, Default)]
+pub struct LeverageStats {
+    batters: HashMap<String, LeverageTotals>,
+    pitchers: HashMap<String, LeverageTotals>,
+}
+
+impl LeverageStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game], leverage_index: &LeverageIndex) -> Self {
+        let mut stats = Self::new();
+        games.iter().for_each(|game| stats.add_game(game, leverage_index));
+        stats
+    }
+
+    pub fn add_game(&mut self, game: &Game, leverage_index: &LeverageIndex) {
+        let starters = [Team::Visiting, Team::Home]
+            .into_iter()
+            .filter_map(|team| {
+                let starter = game.get_starters(&team).into_iter().find(|entry| entry.position == Position::Pitcher)?;
+                Some((team, starter.player_id.clone()))
+            })
+            .collect::<HashMap<Team, String>>();
+        let mut pitchers = HashSet::<&String>::new();
+        let plate_appearances = leverage_index.plate_appearances(game);
+
+        for plate_appearance in &plate_appearances {
+            // the pitcher's first plate appearance is when they came in, even if its leverage
+            // is unknown and it counts for nothing
+            let relief_appearance = plate_appearance.pitcher_id.as_ref().is_some_and(|pitcher_id| {
+                pitchers.insert(pitcher_id) && starters.get(&plate_appearance.state.team.opponent()) != Some(pitcher_id)
+            });
+
+            let Some(leverage) = plate_appearance.leverage_index else {
+                continue;
+            };
+
+            let added = plate_appearance.win_probability_added.unwrap_or_default();
+            let context_neutral = ratio(added, leverage).unwrap_or_default();
+
+            let batter = self.batters.entry(plate_appearance.batter_id.clone()).or_default();
+            batter.plate_appearances += 1;
+            batter.leverage += leverage;
+            batter.win_probability_added += added;
+            batter.context_neutral_wins += context_neutral;
+
+            let Some(pitcher_id) = &plate_appearance.pitcher_id else {
+                continue;
+            };
+
+            let pitcher = self.pitchers.entry(pitcher_id.clone()).or_default();
+            pitcher.plate_appearances += 1;
+            pitcher.leverage += leverage;
+            pitcher.win_probability_added -= added;
+            pitcher.context_neutral_wins -= context_neutral;
+
+            if relief_appearance {
+                pitcher.relief_appearances += 1;
+                pitcher.entering_leverage += leverage;
+            }
+        }
+    }
+
+    pub fn get_batter(&self, player_id: &str) -> Option<&LeverageTotals> {
+        self.batters.get(player_id)
+    }
+
+    pub fn get_pitcher(&self, player_id: &str) -> Option<&LeverageTotals> {
+        self.pitchers.get(player_id)
+    }
+
+    pub fn get_batters(&self) -> &HashMap<String, LeverageTotals> {
+        &self.batters
+    }
+
+    pub fn get_pitchers(&self) -> &HashMap<String, LeverageTotals> {
+        &self.pitchers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::stats::run_expectancy::BaseOutState;
+
+    fn games() -> (Vec<Game>, LeverageIndex) {
+        let games = fixtures::parse(fixtures::LEVERAGE_FILE);
+        let leverage_index = LeverageIndex::from_games(&games, WinExpectancy::from_games(&games));
+        (games, leverage_index)
+    }
+
+    fn state(inning: u8, team: Team, outs: u8, score_difference: i16) -> WinExpectancyState {
+        WinExpectancyState::new(inning, team, BaseOutState::new(outs, [false; 3]), score_difference)
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn leverage_by_state() {
+        let (_, leverage_index) = games();
+
+        // the ten plays swing the game by 5/3 in all, or 1/6 on average, and those from the
+        // start of the game by 1/3, 1/6 and 1/6
+        assert_close(leverage_index.get(&state(1, Team::Visiting, 0, 0)), 4.0 / 3.0);
+        assert_close(leverage_index.get(&state(1, Team::Home, 0, 0)), 3.0);
+        assert_eq!(leverage_index.get(&state(1, Team::Visiting, 1, 0)), Some(0.0));
+        assert_eq!(leverage_index.get(&state(1, Team::Home, 1, 0)), None);
+    }
+
+    #[test]
+    fn plate_appearances_and_players() {
+        let (games, leverage_index) = games();
+
+        let plate_appearances = leverage_index.plate_appearances(&games[0]);
+        assert_eq!(plate_appearances.len(), 3);
+        assert_close(plate_appearances[0].leverage_index, 4.0 / 3.0);
+        assert_close(plate_appearances[0].win_probability_added, 1.0 / 3.0);
+        assert_eq!(plate_appearances[2].pitcher_id.as_deref(), Some("rauch001"));
+
+        let stats = LeverageStats::from_games(&games, &leverage_index);
+
+        let span = stats.get_batter("spand001").unwrap();
+        assert_eq!(span.plate_appearances, 4);
+        assert_close(span.get_average_leverage(), 1.0);
+        assert_close(Some(span.win_probability_added), 0.0);
+
+        let abreu = stats.get_batter("abreb001").unwrap();
+        assert_close(abreu.get_average_leverage(), 2.0);
+
+        let rauch = stats.get_pitcher("rauch001").unwrap();
+        assert_eq!((rauch.relief_appearances, rauch.get_game_entry_leverage()), (1, Some(0.0)));
+        assert_eq!(stats.get_pitcher("bakes002").unwrap().relief_appearances, 0);
+        assert_eq!(stats.get_pitcher("weavj003").unwrap().get_game_entry_leverage(), None);
+    }
+
+    #[test]
+    fn relief_appearance_with_unknown_leverage() {
+        let (_, leverage_index) = games();
+        let games = fixtures::parse(fixtures::RELIEF_FILE);
+
+        let plate_appearances = leverage_index.plate_appearances(&games[0]);
+        assert_eq!(plate_appearances[5].leverage_index, None);
+        assert_eq!(plate_appearances[6].leverage_index, Some(0.0));
+
+        // Rauch's entry is left out of gmLI rather than moved to his next batter
+        let stats = LeverageStats::from_games(&games, &leverage_index);
+        let rauch = stats.get_pitcher("rauch001").unwrap();
+        assert_eq!((rauch.plate_appearances, rauch.relief_appearances, rauch.get_game_entry_leverage()), (1, 0, None));
+    }
+
+    #[test]
+    fn clutch() {
+        let totals = LeverageTotals {
+            plate_appearances: 2,
+            leverage: 3.0,
+            win_probability_added: 0.3,
+            context_neutral_wins: 0.1,
+            ..Default::default()
+        };
+
+        assert_eq!(totals.get_average_leverage(), Some(1.5));
+        assert_close(totals.get_clutch(), 0.1);
+        assert_eq!(LeverageTotals::default().get_clutch(), None);
+    }
+}
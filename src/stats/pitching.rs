@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+use crate::earned_runs::compute_earned_runs;
+use crate::parser::game::{EventType, Game, Play, Position, Team};
+use crate::stats::ratio;
+
+/// Counting stats for a pitcher over any span of games.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitchingTotals {
+    pub games: u32,
+    pub games_started: u32,
+    pub games_finished: u32,
+    pub complete_games: u32,
+    pub shutouts: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub saves: u32,
+    pub outs: u32,
+    pub batters_faced: u32,
+    /// Only from plays that record pitches.
+    pub pitches: u32,
+    /// Pitches that added a strike or were put in play.
+    pub strikes: u32,
+    pub hits: u32,
+    pub runs: u32,
+    pub earned_runs: u32,
+    /// Including intentional walks.
+    pub walks: u32,
+    pub intentional_walks: u32,
+    pub strikeouts: u32,
+    pub home_runs: u32,
+    pub hit_by_pitch: u32,
+    pub wild_pitches: u32,
+    pub balks: u32,
+}
+
+impl PitchingTotals {
+    /// Innings pitched as usually written, where `6.1` is six and a third.
+    pub fn get_innings_pitched(&self) -> String {
+        format!("{}.{}", self.outs / 3, self.outs % 3)
+    }
+
+    pub fn get_earned_run_average(&self) -> Option<f64> {
+        ratio(27.0 * self.earned_runs as f64, self.outs as f64)
+    }
+
+    /// Walks and hits per inning pitched.
+    pub fn get_whip(&self) -> Option<f64> {
+        ratio(3.0 * (self.walks + self.hits) as f64, self.outs as f64)
+    }
+
+    pub fn get_strikeouts_per_nine(&self) -> Option<f64> {
+        ratio(27.0 * self.strikeouts as f64, self.outs as f64)
+    }
+
+    pub fn get_walks_per_nine(&self) -> Option<f64> {
+        ratio(27.0 * self.walks as f64, self.outs as f64)
+    }
+}
+
+impl AddAssign<&PitchingTotals> for PitchingTotals {
+    fn add_assign(&mut self, other: &PitchingTotals) {
+        self.games += other.games;
+        self.games_started += other.games_started;
+        self.games_finished += other.games_finished;
+        self.complete_games += other.complete_games;
+        self.shutouts += other.shutouts;
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.saves += other.saves;
+        self.outs += other.outs;
+        self.batters_faced += other.batters_faced;
+        self.pitches += other.pitches;
+        self.strikes += other.strikes;
+        self.hits += other.hits;
+        self.runs += other.runs;
+        self.earned_runs += other.earned_runs;
+        self.walks += other.walks;
+        self.intentional_walks += other.intentional_walks;
+        self.strikeouts += other.strikeouts;
+        self.home_runs += other.home_runs;
+        self.hit_by_pitch += other.hit_by_pitch;
+        self.wild_pitches += other.wild_pitches;
+        self.balks += other.balks;
+    }
+}
+
+/// Pitching totals by player for every game and season added. Earned runs come from the
+/// file's `data,er` records when it has them and are computed otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct PitchingStats {
+    games: HashMap<String, HashMap<String, PitchingTotals>>,
+    seasons: HashMap<u16, HashMap<String, PitchingTotals>>,
+}
+
+impl PitchingStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game]) -> Self {
+        let mut stats = Self::new();
+        games.iter().for_each(|game| stats.add_game(game));
+        stats
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        let totals = game_totals(game);
+        let season = game.get_season().unwrap_or(0);
+
+        for (player_id, player_totals) in &totals {
+            *self.seasons.entry(season).or_default().entry(player_id.clone()).or_default() += player_totals;
+        }
+        self.games.insert(game.get_id().to_string(), totals);
+    }
+
+    pub fn get_game(&self, game_id: &str, player_id: &str) -> Option<&PitchingTotals> {
+        self.games.get(game_id)?.get(player_id)
+    }
+
+    pub fn get_game_totals(&self, game_id: &str) -> Option<&HashMap<String, PitchingTotals>> {
+        self.games.get(game_id)
+    }
+
+    pub fn get_season(&self, season: u16, player_id: &str) -> Option<&PitchingTotals> {
+        self.seasons.get(&season)?.get(player_id)
+    }
+
+    pub fn get_season_totals(&self, season: u16) -> Option<&HashMap<String, PitchingTotals>> {
+        self.seasons.get(&season)
+    }
+
+    /// The player's totals across every season added.
+    pub fn get_player(&self, player_id: &str) -> PitchingTotals {
+        let mut totals = PitchingTotals::default();
+        for season in self.seasons.values() {
+            if let Some(season_totals) = season.get(player_id) {
+                totals += season_totals;
+            }
+        }
+        totals
+    }
+}
+
+fn game_totals(game: &Game) -> HashMap<String, PitchingTotals> {
+    let mut totals = HashMap::<String, PitchingTotals>::new();
+
+    for play in game.get_plays() {
+        add_play(&mut totals, play);
+    }
+
+    for team in [Team::Visiting, Team::Home] {
+        // the team's pitchers, in the order they appeared
+        let mut pitchers = Vec::<&String>::new();
+        let starter = game.get_starters(&team).into_iter().find(|entry| entry.position == Position::Pitcher);
+        pitchers.extend(starter.map(|entry| &entry.player_id));
+        for play in game.get_plays().iter().filter(|play| play.get_team() == team.opponent()) {
+            if let Some(pitcher_id) = play.get_pitcher_id()
+                && !pitchers.contains(&pitcher_id)
+            {
+                pitchers.push(pitcher_id);
+            }
+        }
+
+        let Some(&first) = pitchers.first() else {
+            continue;
+        };
+        let last = game.get_plays()
+            .iter()
+            .rev()
+            .find(|play| play.get_team() == team.opponent())
+            .and_then(Play::get_pitcher_id)
+            .unwrap_or(first);
+        let runs_allowed = game.get_line_score().get_runs(&team.opponent());
+
+        for &pitcher_id in &pitchers {
+            totals.entry(pitcher_id.clone()).or_default().games += 1;
+        }
+
+        let starter = totals.entry(first.clone()).or_default();
+        starter.games_started += 1;
+        if last == first {
+            starter.complete_games += 1;
+            starter.shutouts += (runs_allowed == 0) as u32;
+        } else {
+            totals.entry(last.clone()).or_default().games_finished += 1;
+        }
+    }
+
+    let info = game.get_info_state();
+    let decided = |pitcher_id: &Option<String>| pitcher_id.clone().filter(|pitcher_id| !pitcher_id.is_empty());
+    if let Some(pitcher_id) = decided(&info.wp) {
+        totals.entry(pitcher_id).or_default().wins += 1;
+    }
+    if let Some(pitcher_id) = decided(&info.lp) {
+        totals.entry(pitcher_id).or_default().losses += 1;
+    }
+    if let Some(pitcher_id) = decided(&info.save) {
+        totals.entry(pitcher_id).or_default().saves += 1;
+    }
+
+    if game.get_earned_runs().is_empty() {
+        for pitcher in compute_earned_runs(game).pitchers {
+            totals.entry(pitcher.pitcher_id).or_default().earned_runs += pitcher.earned_runs as u32;
+        }
+    } else {
+        for (pitcher_id, earned_runs) in game.get_earned_runs() {
+            totals.entry(pitcher_id.clone()).or_default().earned_runs += *earned_runs as u32;
+        }
+    }
+
+    totals
+}
+
+fn add_play(totals: &mut HashMap<String, PitchingTotals>, play: &Play) {
+    let Some(event) = play.get_event() else {
+        return;
+    };
+
+    // runs go to whoever put the runner on base, who may have left the game already
+    for runner_advance in play.get_runner_advances().iter().filter(|ra| ra.advance.is_run()) {
+        if let Some(pitcher_id) = &runner_advance.runner.pitcher_id {
+            totals.entry(pitcher_id.clone()).or_default().runs += 1;
+        }
+    }
+
+    let Some(pitcher_id) = play.get_pitcher_id() else {
+        return;
+    };
+
+    let line = totals.entry(pitcher_id.clone()).or_default();
+    line.outs += play.get_runner_advances().iter().filter(|ra| ra.advance.out).count() as u32;
+
+    let pitches = play.get_pitches_thrown();
+    line.pitches += pitches.len() as u32;
+    line.strikes += pitches.iter().filter(|pitch| pitch.pitch_type.is_strike() || pitch.pitch_type.is_in_play()).count() as u32;
+
+    for event_type in event.event_type.with_base_running_event() {
+        line.wild_pitches += matches!(event_type, EventType::WildPitch) as u32;
+        line.balks += matches!(event_type, EventType::Balk) as u32;
+    }
+
+    if event.event_type.ends_plate_appearance() {
+        line.batters_faced += 1;
+        line.hits += event.event_type.is_hit() as u32;
+        line.walks += matches!(event.event_type, EventType::Walk { .. }) as u32;
+        line.intentional_walks += matches!(event.event_type, EventType::Walk { intentional: true, .. }) as u32;
+        line.strikeouts += matches!(event.event_type, EventType::Strikeout { .. }) as u32;
+        line.home_runs += (event.event_type.total_bases() == 4) as u32;
+        line.hit_by_pitch += matches!(event.event_type, EventType::HitByPitch) as u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::stats::batting::{BattingStats, BattingTotals};
+
+    fn stats() -> PitchingStats {
+        PitchingStats::from_games(&fixtures::parse(fixtures::PITCHING_FILE))
+    }
+
+    #[test]
+    fn game_totals() {
+        let stats = stats();
+
+        let weaver = stats.get_game("ANA201004050", "weavj003").unwrap();
+        assert_eq!((weaver.games_started, weaver.complete_games, weaver.shutouts, weaver.wins), (1, 1, 0, 1));
+        assert_eq!((weaver.outs, weaver.batters_faced, weaver.hits, weaver.runs, weaver.earned_runs), (6, 8, 3, 1, 1));
+        assert_eq!((weaver.pitches, weaver.strikes, weaver.balks), (18, 12, 1));
+
+        let baker = stats.get_game("ANA201004050", "bakes002").unwrap();
+        assert_eq!((baker.games_started, baker.losses, baker.outs, baker.runs, baker.earned_runs), (1, 1, 0, 2, 2));
+        assert_eq!((baker.batters_faced, baker.walks, baker.intentional_walks, baker.home_runs), (3, 1, 1, 1));
+
+        let rauch = stats.get_game("ANA201004050", "rauch001").unwrap();
+        assert_eq!((rauch.games, rauch.games_started, rauch.games_finished, rauch.saves), (1, 0, 1, 0));
+        assert_eq!((rauch.wild_pitches, rauch.strikeouts, rauch.hit_by_pitch, rauch.outs), (1, 1, 1, 6));
+        assert_eq!((rauch.pitches, rauch.strikes), (10, 8));
+    }
+
+    #[test]
+    fn rates() {
+        let stats = stats();
+
+        let weaver = stats.get_season(2010, "weavj003").unwrap();
+        assert_eq!(weaver.get_innings_pitched(), "2.0");
+        assert_eq!(weaver.get_earned_run_average(), Some(4.5));
+        assert_eq!(weaver.get_whip(), Some(1.5));
+
+        let rauch = stats.get_player("rauch001");
+        assert_eq!(rauch.get_strikeouts_per_nine(), Some(4.5));
+        assert_eq!(rauch.get_walks_per_nine(), Some(0.0));
+        assert_eq!(stats.get_player("bakes002").get_earned_run_average(), None);
+    }
+
+    #[test]
+    fn matches_box_score_lines() {
+        let stats = PitchingStats::from_games(&fixtures::games());
+
+        for (player_id, line) in fixtures::box_lines("pline") {
+            let totals = stats.get_game("ANA201004050", &player_id).unwrap();
+            let counts = [
+                totals.outs,
+                totals.batters_faced,
+                totals.hits,
+                totals.home_runs,
+                totals.runs,
+                totals.earned_runs,
+                totals.walks,
+                totals.intentional_walks,
+                totals.strikeouts,
+                totals.hit_by_pitch,
+                totals.wild_pitches,
+                totals.balks,
+            ];
+            // the line also has doubles, triples, sacrifices and batters faced in an unfinished inning
+            let expected = [2, 4, 5, 8, 9, 10, 11, 12, 13, 14, 15, 16].map(|index| line[index]);
+            assert_eq!(counts, expected, "{player_id}");
+        }
+
+        let get = |player_id| stats.get_game("ANA201004050", player_id).unwrap();
+        assert_eq!((get("weavj003").wins, get("rauch001").losses, get("fuenb001").saves), (1, 1, 1));
+    }
+
+    #[test]
+    fn box_score_proof() {
+        let games = fixtures::games();
+        let game = &games[0];
+        let pitching = PitchingStats::from_games(&games).get_game_totals(game.get_id()).unwrap().clone();
+        let batting = BattingStats::from_games(&games).get_game_totals(game.get_id()).unwrap().clone();
+        let line_score = game.get_line_score();
+
+        // what a team's pitchers allowed is what the other team's batters did
+        for team in [Team::Visiting, Team::Home] {
+            let mut allowed = PitchingTotals::default();
+            pitching
+                .iter()
+                .filter(|(player_id, _)| fixtures::plays_for(game, &team, player_id))
+                .for_each(|(_, totals)| allowed += totals);
+            let mut batted = BattingTotals::default();
+            batting
+                .iter()
+                .filter(|(player_id, _)| fixtures::plays_for(game, &team.opponent(), player_id))
+                .for_each(|(_, totals)| batted += totals);
+
+            let allowed_counts = [
+                allowed.batters_faced,
+                allowed.hits,
+                allowed.home_runs,
+                allowed.runs,
+                allowed.walks,
+                allowed.intentional_walks,
+                allowed.strikeouts,
+                allowed.hit_by_pitch,
+            ];
+            let batted_counts = [
+                batted.plate_appearances,
+                batted.hits,
+                batted.home_runs,
+                batted.runs,
+                batted.walks,
+                batted.intentional_walks,
+                batted.strikeouts,
+                batted.hit_by_pitch,
+            ];
+            assert_eq!(allowed_counts, batted_counts, "{team:?}");
+            assert_eq!(allowed.runs, line_score.get_runs(&team.opponent()) as u32, "{team:?}");
+            assert!(allowed.earned_runs <= allowed.runs, "{team:?}");
+        }
+    }
+}
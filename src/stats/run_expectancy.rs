@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::export::csv_writer;
+use crate::parser::game::{Count, EventType, Game, Play};
+use crate::stats::ratio;
+
+/// How many are out and which bases are occupied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BaseOutState {
+    pub outs: u8,
+    /// First, second and third.
+    pub runners: [bool; 3],
+}
+
+impl BaseOutState {
+    pub fn new(outs: u8, runners: [bool; 3]) -> Self {
+        Self { outs, runners }
+    }
+
+    /// The state before the play.
+    pub fn from_play(play: &Play) -> Self {
+        Self::new(play.get_outs(), play.get_bases().clone().map(|runner| runner.is_some()))
+    }
+
+    /// The state once the play is over, or nothing if it ended the half-inning.
+    pub fn after_play(play: &Play) -> Option<Self> {
+        let outs = play.get_outs() + play.get_runner_advances().iter().filter(|ra| ra.advance.out).count() as u8;
+        (outs < 3).then(|| Self::new(outs, play.get_bases_after().map(|runner| runner.is_some())))
+    }
+
+    /// All 24 states, by outs and then by runners as a binary number with first base lowest.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..3).flat_map(|outs| (0..8).map(move |bits: u8| Self::new(outs, [bits & 1 != 0, bits & 2 != 0, bits & 4 != 0])))
+    }
+
+    /// The bases as usually written, such as `1_3` for runners on first and third.
+    pub fn get_bases_code(&self) -> String {
+        self.runners
+            .iter()
+            .zip(['1', '2', '3'])
+            .map(|(occupied, base)| if *occupied { base } else { '_' })
+            .collect()
+    }
+}
+
+/// How often a state came up and the runs scored from then to the end of the half-inning.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunExpectancyCell {
+    pub occurrences: u32,
+    pub runs: u32,
+}
+
+impl RunExpectancyCell {
+    pub fn get_expected_runs(&self) -> Option<f64> {
+        ratio(self.runs as f64, self.occurrences as f64)
+    }
+}
+
+/// The runs a team can expect to score from each base-out state to the end of the half-inning,
+/// for the 24 states on their own (RE24) and for each of them at every count (RE288). Every
+/// play other than `NP` is a visit to its starting state, and every pitch is a visit to the
+/// count it was thrown on. Half-innings that didn't reach three outs, such as walk-offs, are
+/// left out.
+#[derive(Clone, Debug, Default)]
+pub struct RunExpectancy {
+    states: HashMap<BaseOutState, RunExpectancyCell>,
+    count_states: HashMap<(BaseOutState, u8, u8), RunExpectancyCell>,
+}
+
+impl RunExpectancy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games<'a>(games: impl IntoIterator<Item = &'a Game>) -> Self {
+        let mut run_expectancy = Self::new();
+        games.into_iter().for_each(|game| run_expectancy.add_game(game));
+        run_expectancy
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        for half_inning in half_innings(game).into_iter().filter(|plays| is_complete(plays)) {
+            let mut runs_after = half_inning.iter().map(|play| play.get_runs() as u32).sum::<u32>();
+
+            for play in half_inning {
+                let runs = runs_after;
+                runs_after -= play.get_runs() as u32;
+
+                if !is_state(play) {
+                    continue;
+                }
+
+                let state = BaseOutState::from_play(play);
+                let cell = self.states.entry(state).or_default();
+                cell.occurrences += 1;
+                cell.runs += runs;
+
+                for (balls, strikes) in counts_pitched_on(play) {
+                    let cell = self.count_states.entry((state, balls, strikes)).or_default();
+                    cell.occurrences += 1;
+                    cell.runs += runs;
+                }
+            }
+        }
+    }
+
+    pub fn get_cell(&self, state: &BaseOutState) -> Option<&RunExpectancyCell> {
+        self.states.get(state)
+    }
+
+    /// RE24: the expected runs from the state, or nothing if it never came up.
+    pub fn get(&self, state: &BaseOutState) -> Option<f64> {
+        self.get_cell(state)?.get_expected_runs()
+    }
+
+    pub fn get_count_cell(&self, state: &BaseOutState, count: &Count) -> Option<&RunExpectancyCell> {
+        let Count::Known { balls, strikes } = count else {
+            return None;
+        };
+
+        self.count_states.get(&(*state, *balls, *strikes))
+    }
+
+    /// RE288: the expected runs from the state at the count.
+    pub fn get_with_count(&self, state: &BaseOutState, count: &Count) -> Option<f64> {
+        self.get_count_cell(state, count)?.get_expected_runs()
+    }
+
+    /// The play's RE24 run value: the change in expected runs plus the runs it scored. Nothing
+    /// for `NP` or if either state never came up.
+    pub fn get_run_value(&self, play: &Play) -> Option<f64> {
+        if !is_state(play) {
+            return None;
+        }
+
+        let before = self.get(&BaseOutState::from_play(play))?;
+        let after = match BaseOutState::after_play(play) {
+            Some(state) => self.get(&state)?,
+            None => 0.0,
+        };
+
+        Some(after - before + play.get_runs() as f64)
+    }
+
+    /// Writes the 24 states as CSV, leaving the expected runs blank for any that never came up.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), String> {
+        let mut writer = csv_writer(writer);
+
+        writer.write_record(["outs", "bases", "occurrences", "runs", "expected_runs"]).map_err(|e| e.to_string())?;
+        for state in BaseOutState::all() {
+            let cell = self.get_cell(&state).cloned().unwrap_or_default();
+            let mut row = vec![state.outs.to_string(), state.get_bases_code()];
+            row.extend(cell_fields(&cell));
+            writer.write_record(row).map_err(|e| e.to_string())?;
+        }
+
+        writer.flush().map_err(|e| e.to_string())
+    }
+
+    /// Writes the 288 state and count pairs as CSV.
+    pub fn write_count_csv<W: Write>(&self, writer: W) -> Result<(), String> {
+        let mut writer = csv_writer(writer);
+
+        writer
+            .write_record(["outs", "bases", "balls", "strikes", "occurrences", "runs", "expected_runs"])
+            .map_err(|e| e.to_string())?;
+        for state in BaseOutState::all() {
+            for (balls, strikes) in (0..4).flat_map(|balls| (0..3).map(move |strikes| (balls, strikes))) {
+                let cell = self.count_states.get(&(state, balls, strikes)).cloned().unwrap_or_default();
+                let mut row = vec![state.outs.to_string(), state.get_bases_code(), balls.to_string(), strikes.to_string()];
+                row.extend(cell_fields(&cell));
+                writer.write_record(row).map_err(|e| e.to_string())?;
+            }
+        }
+
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+/// The plays of each half-inning, in order.
+pub(crate) fn half_innings(game: &Game) -> Vec<&[Play]> {
+    game.get_plays()
+        .chunk_by(|a, b| a.get_inning() == b.get_inning() && a.get_team() == b.get_team())
+        .collect()
+}
+
+fn is_complete(half_inning: &[Play]) -> bool {
+    half_inning.iter().any(|play| is_state(play) && BaseOutState::after_play(play).is_none())
+}
+
+/// Whether the play happened in its own right, rather than being an `NP` placeholder for a
+/// substitution.
+pub(crate) fn is_state(play: &Play) -> bool {
+    play.get_event().is_some_and(|event| event.event_type != EventType::NoPlay)
+}
+
+/// The count before each pitch thrown during the play. Pitches repeated from earlier in an
+/// interrupted plate appearance were counted with the play they were thrown in.
+fn counts_pitched_on(play: &Play) -> Vec<(u8, u8)> {
+    let mut counts = vec![Count::Known { balls: 0, strikes: 0 }];
+    counts.extend(play.get_count_progression().into_iter().map(|(_, count)| count));
+    counts.pop();
+
+    let thrown = play.get_pitches_thrown().len();
+    counts[counts.len().saturating_sub(thrown)..]
+        .iter()
+        .filter_map(|count| match count {
+            Count::Known { balls, strikes } if *balls < 4 && *strikes < 3 => Some((*balls, *strikes)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn cell_fields(cell: &RunExpectancyCell) -> [String; 3] {
+    [
+        cell.occurrences.to_string(),
+        cell.runs.to_string(),
+        cell.get_expected_runs().map(|runs| format!("{runs:.3}")).unwrap_or_default(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn run_expectancy() -> RunExpectancy {
+        RunExpectancy::from_games(&fixtures::parse(fixtures::RUN_EXPECTANCY_FILE))
+    }
+
+    #[test]
+    fn base_out_states() {
+        let states = BaseOutState::all().collect::<Vec<BaseOutState>>();
+        assert_eq!(states.len(), 24);
+        assert_eq!(states[5], BaseOutState::new(0, [true, false, true]));
+        assert_eq!(states[5].get_bases_code(), "1_3");
+        assert_eq!(states[23].get_bases_code(), "123");
+    }
+
+    #[test]
+    fn expected_runs() {
+        let run_expectancy = run_expectancy();
+        let empty = BaseOutState::new(0, [false; 3]);
+
+        // the unfinished top of the second is left out
+        assert_eq!(run_expectancy.get_cell(&empty), Some(&RunExpectancyCell { occurrences: 3, runs: 2 }));
+        assert_eq!(run_expectancy.get(&BaseOutState::new(0, [true, false, false])), Some(1.0));
+        assert_eq!(run_expectancy.get(&BaseOutState::new(1, [true, false, false])), Some(0.0));
+        assert_eq!(run_expectancy.get_cell(&BaseOutState::new(2, [false; 3])).unwrap().occurrences, 1);
+        assert_eq!(run_expectancy.get(&BaseOutState::new(1, [false, true, true])), None);
+
+        let two_one = Count::Known { balls: 2, strikes: 1 };
+        let cell = run_expectancy.get_count_cell(&BaseOutState::new(1, [false, true, false]), &two_one);
+        assert_eq!(cell, Some(&RunExpectancyCell { occurrences: 1, runs: 0 }));
+        assert_eq!(run_expectancy.get_count_cell(&BaseOutState::new(1, [true, false, false]), &two_one), None);
+        assert_eq!(run_expectancy.get_with_count(&empty, &Count::Known { balls: 1, strikes: 0 }), Some(2.0));
+        assert_eq!(run_expectancy.get_with_count(&empty, &Count::Unknown), None);
+    }
+
+    #[test]
+    fn writes_csv() {
+        let run_expectancy = run_expectancy();
+
+        let mut output = Vec::new();
+        run_expectancy.write_csv(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 25);
+        assert_eq!(lines[0], "outs,bases,occurrences,runs,expected_runs");
+        assert_eq!(lines[1], "0,___,3,2,0.667");
+        assert_eq!(lines[24], "2,123,0,0,");
+
+        let mut output = Vec::new();
+        run_expectancy.write_count_csv(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 289);
+        assert_eq!(output.lines().nth(2), Some("0,___,0,1,1,0,0.000"));
+    }
+}
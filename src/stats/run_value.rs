@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use crate::chadwick::EventCategory;
+use crate::parser::game::Game;
+use crate::stats::{credited_players, ratio};
+use crate::stats::run_expectancy::RunExpectancy;
+
+/// The RE24 run values of every play in one event category.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoryRunValue {
+    pub occurrences: u32,
+    pub run_value: f64,
+}
+
+impl CategoryRunValue {
+    /// The average run value of the category.
+    pub fn get_weight(&self) -> Option<f64> {
+        ratio(self.run_value, self.occurrences as f64)
+    }
+}
+
+/// Linear weights: the average RE24 run value of each event category. A league is whatever set
+/// of games is added, measured against a run expectancy matrix built from the same games.
+#[derive(Clone, Debug, Default)]
+pub struct LinearWeights {
+    categories: HashMap<EventCategory, CategoryRunValue>,
+}
+
+impl LinearWeights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games<'a>(games: impl IntoIterator<Item = &'a Game>, run_expectancy: &RunExpectancy) -> Self {
+        let mut linear_weights = Self::new();
+        games.into_iter().for_each(|game| linear_weights.add_game(game, run_expectancy));
+        linear_weights
+    }
+
+    /// Weights for each season, each measured against that season's own run expectancy.
+    pub fn by_season(games: &[Game]) -> HashMap<u16, Self> {
+        let mut seasons = HashMap::<u16, Vec<&Game>>::new();
+        for game in games {
+            seasons.entry(game.get_season().unwrap_or(0)).or_default().push(game);
+        }
+
+        seasons
+            .into_iter()
+            .map(|(season, games)| {
+                let run_expectancy = RunExpectancy::from_games(games.iter().copied());
+                (season, Self::from_games(games.iter().copied(), &run_expectancy))
+            })
+            .collect()
+    }
+
+    pub fn add_game(&mut self, game: &Game, run_expectancy: &RunExpectancy) {
+        for play in game.get_plays() {
+            let (Some(event), Some(run_value)) = (play.get_event(), run_expectancy.get_run_value(play)) else {
+                continue;
+            };
+
+            let category = self.categories.entry(EventCategory::from_event(event)).or_default();
+            category.occurrences += 1;
+            category.run_value += run_value;
+        }
+    }
+
+    pub fn get(&self, category: &EventCategory) -> Option<&CategoryRunValue> {
+        self.categories.get(category)
+    }
+
+    pub fn get_weight(&self, category: &EventCategory) -> Option<f64> {
+        self.get(category)?.get_weight()
+    }
+
+    /// The average run value of an out made by the batter, strikeouts included.
+    pub fn get_out_weight(&self) -> Option<f64> {
+        let outs = [EventCategory::GenericOut, EventCategory::Strikeout]
+            .iter()
+            .filter_map(|category| self.get(category))
+            .fold(CategoryRunValue::default(), |total, category| CategoryRunValue {
+                occurrences: total.occurrences + category.occurrences,
+                run_value: total.run_value + category.run_value,
+            });
+
+        outs.get_weight()
+    }
+
+    /// The weight of the category above that of an out, as used for wOBA before scaling.
+    pub fn get_weight_over_out(&self, category: &EventCategory) -> Option<f64> {
+        Some(self.get_weight(category)? - self.get_out_weight()?)
+    }
+}
+
+/// RE24 totals by player. Batters are credited with the plays that end their plate appearances
+/// and runners with the steals and pickoffs that name them, shared evenly on a double steal.
+/// Pitchers are credited with every play while they pitched. A pitcher's total is runs saved,
+/// so it's the negative of what the plays were worth to the batting team.
+#[derive(Clone, Debug, Default)]
+pub struct Re24Totals {
+    batters: HashMap<String, f64>,
+    pitchers: HashMap<String, f64>,
+}
+
+impl Re24Totals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game], run_expectancy: &RunExpectancy) -> Self {
+        let mut totals = Self::new();
+        games.iter().for_each(|game| totals.add_game(game, run_expectancy));
+        totals
+    }
+
+    pub fn add_game(&mut self, game: &Game, run_expectancy: &RunExpectancy) {
+        for play in game.get_plays() {
+            let Some(run_value) = run_expectancy.get_run_value(play) else {
+                continue;
+            };
+
+            let players = credited_players(play);
+            for player_id in &players {
+                *self.batters.entry(player_id.clone()).or_default() += run_value / players.len() as f64;
+            }
+            if let Some(pitcher_id) = play.get_pitcher_id() {
+                *self.pitchers.entry(pitcher_id.clone()).or_default() -= run_value;
+            }
+        }
+    }
+
+    pub fn get_batter(&self, player_id: &str) -> f64 {
+        self.batters.get(player_id).copied().unwrap_or_default()
+    }
+
+    pub fn get_pitcher(&self, player_id: &str) -> f64 {
+        self.pitchers.get(player_id).copied().unwrap_or_default()
+    }
+
+    pub fn get_batters(&self) -> &HashMap<String, f64> {
+        &self.batters
+    }
+
+    pub fn get_pitchers(&self) -> &HashMap<String, f64> {
+        &self.pitchers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::parser::FileParser;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    fn games() -> (Vec<Game>, RunExpectancy) {
+        let games = fixtures::parse(fixtures::RUN_EXPECTANCY_FILE);
+        let run_expectancy = RunExpectancy::from_games(&games);
+        (games, run_expectancy)
+    }
+
+    #[test]
+    fn run_values() {
+        let (games, run_expectancy) = games();
+        let plays = games[0].get_plays();
+
+        // nobody on and nobody out is worth 2/3 of a run and a runner on first 1
+        assert_close(run_expectancy.get_run_value(&plays[0]), 1.0 / 3.0);
+        assert_close(run_expectancy.get_run_value(&plays[1]), 5.0 / 3.0);
+        assert_close(run_expectancy.get_run_value(&plays[8]), -1.0);
+        assert_eq!(run_expectancy.get_run_value(&plays[9]), None);
+        assert_close(run_expectancy.get_run_value(&plays[11]), 1.0);
+    }
+
+    #[test]
+    fn weights() {
+        let (games, run_expectancy) = games();
+        let linear_weights = LinearWeights::from_games(&games, &run_expectancy);
+
+        assert_eq!(linear_weights.get(&EventCategory::Single).unwrap().occurrences, 3);
+        assert_close(linear_weights.get_weight(&EventCategory::Single), 2.0 / 9.0);
+        assert_close(linear_weights.get_weight(&EventCategory::HomeRun), 4.0 / 3.0);
+        assert_close(linear_weights.get_weight(&EventCategory::GenericOut), -0.5);
+        assert_close(linear_weights.get_out_weight(), -1.0 / 3.0);
+        assert_close(linear_weights.get_weight_over_out(&EventCategory::Single), 5.0 / 9.0);
+        assert_eq!(linear_weights.get_weight(&EventCategory::Walk), None);
+
+        let seasons = LinearWeights::by_season(&games);
+        assert_eq!(seasons.len(), 1);
+        assert_eq!(seasons[&2010].get(&EventCategory::StolenBase), linear_weights.get(&EventCategory::StolenBase));
+    }
+
+    #[test]
+    fn player_totals() {
+        let (games, run_expectancy) = games();
+        let totals = Re24Totals::from_games(&games, &run_expectancy);
+
+        assert_close(Some(totals.get_batter("spand001")), 4.0 / 3.0);
+        assert_close(Some(totals.get_batter("hudso001")), 5.0 / 3.0);
+        assert_close(Some(totals.get_pitcher("weavj003")), -7.0 / 3.0);
+        assert_close(Some(totals.get_pitcher("bakes002")), 2.0 / 3.0);
+        assert_eq!(totals.get_batter("weavj003"), 0.0);
+    }
+
+    #[test]
+    fn stolen_bases_are_credited_to_the_runner() {
+        let file = "id,ANA201004050
+version,2
+info,visteam,MIN
+info,hometeam,ANA
+start,spand001,\"Denard Span\",0,1,8
+start,hudso001,\"Orlando Hudson\",0,2,4
+start,mauej001,\"Joe Mauer\",0,3,2
+start,weavj003,\"Jered Weaver\",1,0,1
+play,1,0,spand001,00,X,S7
+play,1,0,hudso001,00,,SB2
+play,1,0,hudso001,00,X,S8.2-H
+play,1,0,mauej001,00,X,K
+play,1,0,mauej001,00,X,64(1)3/GDP";
+        let games = FileParser::new().parse_file(file).unwrap();
+        let run_expectancy = RunExpectancy::from_games(&games);
+        let totals = Re24Totals::from_games(&games, &run_expectancy);
+
+        // a runner on second with nobody out is worth a run and on first half a run
+        assert_close(run_expectancy.get_run_value(&games[0].get_plays()[1]), 0.5);
+        assert_close(Some(totals.get_batter("spand001")), -0.5 + 0.5);
+        assert_close(Some(totals.get_batter("hudso001")), 0.5);
+        assert_close(Some(totals.get_pitcher("weavj003")), 0.0);
+    }
+}
@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::export::csv_writer;
+use crate::parser::game::{Game, Play, Team};
+use crate::stats::{credited_players, ratio};
+use crate::stats::run_expectancy::{BaseOutState, is_state};
+
+/// Extra innings are treated as the ninth.
+const LAST_INNING: u8 = 9;
+/// Bigger leads are treated as ten runs.
+const MAX_SCORE_DIFFERENCE: i16 = 10;
+
+/// The situation before a play, as far as who's likely to win is concerned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WinExpectancyState {
+    pub inning: u8,
+    /// The team batting.
+    pub team: Team,
+    pub base_out_state: BaseOutState,
+    /// The home team's runs less the visiting team's.
+    pub score_difference: i16,
+}
+
+impl WinExpectancyState {
+    pub fn new(inning: u8, team: Team, base_out_state: BaseOutState, score_difference: i16) -> Self {
+        Self {
+            inning: inning.min(LAST_INNING),
+            team,
+            base_out_state,
+            score_difference: score_difference.clamp(-MAX_SCORE_DIFFERENCE, MAX_SCORE_DIFFERENCE),
+        }
+    }
+
+    pub fn from_play(play: &Play, visiting_score: u8, home_score: u8) -> Self {
+        Self::new(
+            play.get_inning(),
+            play.get_team(),
+            BaseOutState::from_play(play),
+            home_score as i16 - visiting_score as i16,
+        )
+    }
+}
+
+/// How often a state came up and how often the home team went on to win from it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WinExpectancyCell {
+    pub occurrences: u32,
+    pub home_wins: u32,
+}
+
+impl WinExpectancyCell {
+    pub fn get_home_win_probability(&self) -> Option<f64> {
+        ratio(self.home_wins as f64, self.occurrences as f64)
+    }
+}
+
+/// A play with the home team's chance of winning before and after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayWinProbability {
+    /// The play's place among the game's plays.
+    pub play_index: usize,
+    pub state: WinExpectancyState,
+    pub batter_id: String,
+    pub pitcher_id: Option<String>,
+    pub home_win_probability_before: Option<f64>,
+    pub home_win_probability_after: Option<f64>,
+}
+
+impl PlayWinProbability {
+    /// What the play added to the batting team's chance of winning.
+    pub fn get_win_probability_added(&self) -> Option<f64> {
+        let added = self.home_win_probability_after? - self.home_win_probability_before?;
+        match self.state.team {
+            Team::Visiting => Some(-added),
+            Team::Home => Some(added),
+        }
+    }
+}
+
+/// An empirical win expectancy table: the share of games the home team won from each inning,
+/// half, base-out state and score. Games that ended tied are left out.
+#[derive(Clone, Debug, Default)]
+pub struct WinExpectancy {
+    states: HashMap<WinExpectancyState, WinExpectancyCell>,
+}
+
+impl WinExpectancy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game]) -> Self {
+        let mut win_expectancy = Self::new();
+        games.iter().for_each(|game| win_expectancy.add_game(game));
+        win_expectancy
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        let Some(home_won) = home_won(game) else {
+            return;
+        };
+
+        for (_, state) in play_states(game) {
+            let cell = self.states.entry(state).or_default();
+            cell.occurrences += 1;
+            cell.home_wins += home_won as u32;
+        }
+    }
+
+    pub fn get_cell(&self, state: &WinExpectancyState) -> Option<&WinExpectancyCell> {
+        self.states.get(state)
+    }
+
+    /// The home team's chance of winning from the state, or nothing if it never came up.
+    pub fn get(&self, state: &WinExpectancyState) -> Option<f64> {
+        self.get_cell(state)?.get_home_win_probability()
+    }
+
+    /// Every play in the game other than `NP`. The chance after a play is the chance before
+    /// the next one, or the result once the game is over.
+    pub fn plays(&self, game: &Game) -> Vec<PlayWinProbability> {
+        let states = play_states(game);
+        let result = home_won(game).map(|home_won| if home_won { 1.0 } else { 0.0 });
+
+        states
+            .iter()
+            .enumerate()
+            .map(|(index, (play_index, state))| {
+                let play = &game.get_plays()[*play_index];
+                let after = match states.get(index + 1) {
+                    Some((_, next_state)) => self.get(next_state),
+                    None => result,
+                };
+
+                PlayWinProbability {
+                    play_index: *play_index,
+                    state: *state,
+                    batter_id: play.get_batter_id().clone(),
+                    pitcher_id: play.get_pitcher_id().cloned(),
+                    home_win_probability_before: self.get(state),
+                    home_win_probability_after: after,
+                }
+            })
+            .collect()
+    }
+
+    /// Writes the game's win probability chart as CSV, one row per play, with the home team's
+    /// chances and the batting team's win probability added.
+    pub fn write_game_csv<W: Write>(&self, game: &Game, writer: W) -> Result<(), String> {
+        let mut writer = csv_writer(writer);
+        let format = |value: Option<f64>| value.map(|value| format!("{value:.3}")).unwrap_or_default();
+
+        writer
+            .write_record([
+                "game_id",
+                "play_index",
+                "inning",
+                "half",
+                "outs",
+                "bases",
+                "score_difference",
+                "batter_id",
+                "pitcher_id",
+                "event_text",
+                "home_win_probability_before",
+                "home_win_probability_after",
+                "win_probability_added",
+            ])
+            .map_err(|e| e.to_string())?;
+
+        for play_win_probability in self.plays(game) {
+            let play = &game.get_plays()[play_win_probability.play_index];
+            let state = &play_win_probability.state;
+
+            writer
+                .write_record([
+                    game.get_id().clone(),
+                    play_win_probability.play_index.to_string(),
+                    play.get_inning().to_string(),
+                    match state.team {
+                        Team::Visiting => "top",
+                        Team::Home => "bottom",
+                    }
+                    .to_string(),
+                    state.base_out_state.outs.to_string(),
+                    state.base_out_state.get_bases_code(),
+                    state.score_difference.to_string(),
+                    play_win_probability.batter_id.clone(),
+                    play_win_probability.pitcher_id.clone().unwrap_or_default(),
+                    play.get_event_text().clone(),
+                    format(play_win_probability.home_win_probability_before),
+                    format(play_win_probability.home_win_probability_after),
+                    format(play_win_probability.get_win_probability_added()),
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+/// Win probability added by player. Plays are credited to batters and runners as in
+/// [`Re24Totals`](crate::stats::run_value::Re24Totals), and pitchers with the opposite of
+/// every play while they pitched.
+#[derive(Clone, Debug, Default)]
+pub struct WpaTotals {
+    batters: HashMap<String, f64>,
+    pitchers: HashMap<String, f64>,
+}
+
+impl WpaTotals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_games(games: &[Game], win_expectancy: &WinExpectancy) -> Self {
+        let mut totals = Self::new();
+        games.iter().for_each(|game| totals.add_game(game, win_expectancy));
+        totals
+    }
+
+    pub fn add_game(&mut self, game: &Game, win_expectancy: &WinExpectancy) {
+        for play_win_probability in win_expectancy.plays(game) {
+            let Some(added) = play_win_probability.get_win_probability_added() else {
+                continue;
+            };
+
+            let players = credited_players(&game.get_plays()[play_win_probability.play_index]);
+            for player_id in &players {
+                *self.batters.entry(player_id.clone()).or_default() += added / players.len() as f64;
+            }
+            if let Some(pitcher_id) = play_win_probability.pitcher_id {
+                *self.pitchers.entry(pitcher_id).or_default() -= added;
+            }
+        }
+    }
+
+    pub fn get_batter(&self, player_id: &str) -> f64 {
+        self.batters.get(player_id).copied().unwrap_or_default()
+    }
+
+    pub fn get_pitcher(&self, player_id: &str) -> f64 {
+        self.pitchers.get(player_id).copied().unwrap_or_default()
+    }
+
+    pub fn get_batters(&self) -> &HashMap<String, f64> {
+        &self.batters
+    }
+
+    pub fn get_pitchers(&self) -> &HashMap<String, f64> {
+        &self.pitchers
+    }
+}
+
+/// Whether the home team won, or nothing if the game ended tied.
+fn home_won(game: &Game) -> Option<bool> {
+    let line_score = game.get_line_score();
+    let (visiting, home) = (line_score.get_runs(&Team::Visiting), line_score.get_runs(&Team::Home));
+    (visiting != home).then_some(home > visiting)
+}
+
+/// The index and starting state of every play other than `NP`.
+fn play_states(game: &Game) -> Vec<(usize, WinExpectancyState)> {
+    let (mut visiting_score, mut home_score) = (0, 0);
+    let mut states = Vec::new();
+
+    for (index, play) in game.get_plays().iter().enumerate() {
+        if is_state(play) {
+            states.push((index, WinExpectancyState::from_play(play, visiting_score, home_score)));
+        }
+
+        match play.get_team() {
+            Team::Visiting => visiting_score += play.get_runs(),
+            Team::Home => home_score += play.get_runs(),
+        }
+    }
+
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::parser::FileParser;
+
+    fn games() -> (Vec<Game>, WinExpectancy) {
+        let games = fixtures::parse(fixtures::WIN_EXPECTANCY_FILE);
+        let win_expectancy = WinExpectancy::from_games(&games);
+        (games, win_expectancy)
+    }
+
+    fn state(inning: u8, team: Team, outs: u8, score_difference: i16) -> WinExpectancyState {
+        WinExpectancyState::new(inning, team, BaseOutState::new(outs, [false; 3]), score_difference)
+    }
+
+    #[test]
+    fn home_win_probabilities() {
+        let (_, win_expectancy) = games();
+
+        // the tied third game is left out
+        let start = state(1, Team::Visiting, 0, 0);
+        assert_eq!(win_expectancy.get_cell(&start), Some(&WinExpectancyCell { occurrences: 2, home_wins: 1 }));
+        assert_eq!(win_expectancy.get(&start), Some(0.5));
+        assert_eq!(win_expectancy.get(&state(1, Team::Visiting, 0, -1)), Some(0.0));
+        assert_eq!(win_expectancy.get(&state(1, Team::Home, 0, 1)), Some(1.0));
+        assert_eq!(win_expectancy.get(&state(2, Team::Visiting, 0, 0)), None);
+
+        assert_eq!(state(12, Team::Home, 0, -15), state(9, Team::Home, 0, -10));
+    }
+
+    #[test]
+    fn win_probability_added() {
+        let (games, win_expectancy) = games();
+
+        let plays = win_expectancy.plays(&games[1]);
+        assert_eq!(plays.iter().map(|play| play.play_index).collect::<Vec<usize>>(), [0, 2, 3]);
+        assert_eq!((plays[0].home_win_probability_before, plays[0].home_win_probability_after), (Some(0.5), Some(1.0)));
+        assert_eq!(plays[0].get_win_probability_added(), Some(-0.5));
+        assert_eq!(plays[2].home_win_probability_after, Some(1.0));
+
+        let tied = win_expectancy.plays(&games[2]);
+        assert_eq!((tied[0].home_win_probability_before, tied[0].home_win_probability_after), (Some(0.5), None));
+
+        let totals = WpaTotals::from_games(&games[..1], &win_expectancy);
+        assert_eq!(totals.get_batter("spand001"), 0.5);
+        assert_eq!(totals.get_pitcher("weavj003"), -0.5);
+        assert_eq!(totals.get_batter("abreb001"), 0.0);
+        assert_eq!(totals.get_pitcher("bakes002"), 0.0);
+    }
+
+    #[test]
+    fn stolen_bases_are_credited_to_the_runner() {
+        let header = "version,2
+info,visteam,MIN
+info,hometeam,ANA
+start,spand001,\"Denard Span\",0,1,8
+start,hudso001,\"Orlando Hudson\",0,2,4
+start,mauej001,\"Joe Mauer\",0,3,2
+start,cuddm001,\"Michael Cuddyer\",0,4,9
+start,bakes002,\"Scott Baker\",0,0,1
+start,abreb001,\"Bobby Abreu\",1,1,9
+start,weavj003,\"Jered Weaver\",1,0,1";
+        let file = format!(
+            "id,ANA201004050\n{header}
+play,1,0,spand001,00,X,S7
+play,1,0,hudso001,00,,SB2
+play,1,0,hudso001,00,X,K
+play,1,0,mauej001,00,X,K
+play,1,0,cuddm001,00,X,K
+play,1,1,abreb001,00,X,HR/F9
+id,ANA201004060\n{header}
+play,1,0,spand001,00,X,S7
+play,1,0,hudso001,00,X,K
+play,1,0,mauej001,00,X,K
+play,1,0,cuddm001,00,X,K
+play,1,1,abreb001,00,X,K
+play,2,0,spand001,00,X,HR/F9"
+        );
+        let games = FileParser::new().parse_file(&file).unwrap();
+        let win_expectancy = WinExpectancy::from_games(&games);
+        let totals = WpaTotals::from_games(&games[..1], &win_expectancy);
+
+        // only the first game's visitors got a runner to second, and they went on to lose
+        assert_eq!(win_expectancy.plays(&games[0])[1].get_win_probability_added(), Some(-0.5));
+        assert_eq!(totals.get_batter("spand001"), -0.5);
+        assert_eq!(totals.get_batter("hudso001"), 0.0);
+        assert_eq!(totals.get_batter("cuddm001"), 0.5);
+    }
+
+    #[test]
+    fn writes_game_csv() {
+        let (games, win_expectancy) = games();
+
+        let mut output = Vec::new();
+        win_expectancy.write_game_csv(&games[0], &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("game_id,play_index,inning,half,outs,bases,score_difference"));
+        assert_eq!(lines[1], "ANA201004050,0,1,top,0,___,0,spand001,weavj003,HR/F9,0.500,0.000,0.500");
+        assert_eq!(lines[3], "ANA201004050,2,1,bottom,0,___,-1,abreb001,bakes002,8/F8,0.000,0.000,0.000");
+    }
+}
@@ -0,0 +1,249 @@
+use std::io::Write;
+
+use crate::parser::Info;
+use crate::parser::game::{Adjustment, AttendanceInfo, Game, LineupEntry, Play, TemperatureInfo, TimeOfGameInfo, WindSpeedInfo};
+
+/// Writes games back out as Retrosheet event files. Events are written from the parsed model
+/// rather than copied from the original text, so a corrected play is written as it now stands.
+/// Only the info records the game was read with are written, in the order they came in.
+pub struct EventFileWriter;
+
+impl Default for EventFileWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventFileWriter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// The lines of one game, from its `id` record to its `data` records.
+    pub fn lines(&self, game: &Game) -> Vec<String> {
+        let mut lines = vec![
+            format!("id,{}", game.get_id()),
+            format!("version,{}", game.get_version()),
+        ];
+
+        lines.extend(game.get_info_records().iter().map(info_line));
+
+        let entries = game.get_lineup_entries();
+        lines.extend(entries.iter().filter(|entry| entry.is_start).map(lineup_line));
+        lines.extend(game.get_comments().iter().map(|comment| comment_line(comment)));
+
+        let adjustments = game.get_adjustments();
+        for (index, play) in game.get_plays().iter().enumerate() {
+            lines.extend(entries.iter().filter(|entry| !entry.is_start && entry.play_index == index).map(lineup_line));
+            lines.extend(adjustments.iter().filter(|entry| entry.play_index == index).map(|entry| adjustment_line(&entry.adjustment)));
+            lines.push(play_line(play));
+            lines.extend(play.get_comments().iter().map(|comment| comment_line(comment)));
+        }
+
+        // substitutions after the last play, such as a defensive change with the game over
+        let play_count = game.get_plays().len();
+        lines.extend(entries.iter().filter(|entry| !entry.is_start && entry.play_index >= play_count).map(lineup_line));
+
+        lines.extend(game.get_earned_runs().iter().map(|(player_id, earned_runs)| format!("data,er,{player_id},{earned_runs}")));
+        lines
+    }
+
+    /// One game as the text of an event file.
+    pub fn write_game(&self, game: &Game) -> String {
+        self.lines(game).iter().map(|line| format!("{line}\n")).collect()
+    }
+
+    pub fn write<W: Write>(&self, games: &[Game], mut writer: W) -> Result<(), String> {
+        for game in games {
+            writer.write_all(self.write_game(game).as_bytes()).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+fn info_line(info: &Info) -> String {
+    let umpire = |umpire: &Option<String>| umpire.clone().unwrap_or("(none)".to_string());
+
+    let (key, value) = match info {
+        Info::VisitingTeam(visiting_team) => ("visteam", visiting_team.clone()),
+        Info::HomeTeam(home_team) => ("hometeam", home_team.clone()),
+        Info::Date(date) => ("date", date.clone()),
+        Info::Number(number) => ("number", number.to_string()),
+        Info::StartTime(start_time) => ("starttime", start_time.clone()),
+        Info::DayNight(day_night) => ("daynight", day_night.to_string()),
+        Info::Innings(innings) => ("innings", innings.to_string()),
+        Info::Tiebreaker(tiebreaker) => ("tiebreaker", tiebreaker.to_string()),
+        Info::UsedDesignatedHitterRule(usedh) => ("usedh", usedh.to_string()),
+        Info::Pitches(pitches) => ("pitches", pitches.to_string()),
+        Info::OfficialScorer(official_scorer) => ("oscorer", official_scorer.clone()),
+        Info::HomeTeamBatFirst(htbf) => ("htbf", htbf.to_string()),
+        Info::UmpireHome(umpire_home) => ("umphome", umpire(umpire_home)),
+        Info::Umpire1B(umpire_1b) => ("ump1b", umpire(umpire_1b)),
+        Info::Umpire2B(umpire_2b) => ("ump2b", umpire(umpire_2b)),
+        Info::Umpire3B(umpire_3b) => ("ump3b", umpire(umpire_3b)),
+        Info::UmpireLeftField(umpire_left_field) => ("umplf", umpire(umpire_left_field)),
+        Info::UmpireRightField(umpire_right_field) => ("umprf", umpire(umpire_right_field)),
+        Info::FieldCondition(field_condition) => ("fieldcond", field_condition.to_string()),
+        Info::Precipitation(precipitation) => ("precip", precipitation.to_string()),
+        Info::Sky(sky) => ("sky", sky.to_string()),
+        Info::Temperature(temperature) => ("temp", match temperature {
+            TemperatureInfo::Known(temperature) => temperature.to_string(),
+            TemperatureInfo::Unknown => "0".to_string(),
+        }),
+        Info::WindDirection(wind_direction) => ("winddir", wind_direction.to_string()),
+        Info::WindSpeed(wind_speed) => ("windspeed", match wind_speed {
+            WindSpeedInfo::Known(wind_speed) => wind_speed.to_string(),
+            WindSpeedInfo::Unknown => "-1".to_string(),
+        }),
+        Info::TimeOfGame(time_of_game) => ("timeofgame", match time_of_game {
+            TimeOfGameInfo::Known(minutes) => minutes.to_string(),
+            TimeOfGameInfo::Unknown => "0".to_string(),
+        }),
+        Info::Attendance(attendance) => ("attendance", match attendance {
+            AttendanceInfo::Known(attendance) => attendance.to_string(),
+            AttendanceInfo::Unknown => "0".to_string(),
+        }),
+        Info::Site(site) => ("site", site.clone()),
+        Info::WP(wp) => ("wp", wp.clone()),
+        Info::LP(lp) => ("lp", lp.clone()),
+        Info::Save(save) => ("save", save.clone().unwrap_or_default()),
+        Info::GameWinningRBI(game_winning_rbi) => ("gwrbi", game_winning_rbi.clone().unwrap_or_default()),
+        Info::GameType(game_type) => ("gametype", game_type.to_string()),
+        Info::Other(key, value) => (key.as_str(), value.clone()),
+    };
+
+    format!("info,{key},{value}")
+}
+
+fn lineup_line(entry: &LineupEntry) -> String {
+    format!(
+        "{},{},\"{}\",{},{},{}",
+        if entry.is_start { "start" } else { "sub" },
+        entry.player_id,
+        entry.player_name,
+        entry.team,
+        entry.batting_order,
+        entry.position,
+    )
+}
+
+fn adjustment_line(adjustment: &Adjustment) -> String {
+    match adjustment {
+        Adjustment::Runner { player_id, base } => format!("radj,{player_id},{}", base.index().map_or(0, |index| index + 1)),
+        Adjustment::BattingHand { player_id, hand } => format!("badj,{player_id},{hand}"),
+        Adjustment::PitchingHand { player_id, hand } => format!("padj,{player_id},{hand}"),
+        Adjustment::Lineup { team, batting_order } => format!("ladj,{team},{batting_order}"),
+    }
+}
+
+fn play_line(play: &Play) -> String {
+    let count = play.get_count().map(|count| count.to_string()).unwrap_or("??".to_string());
+    let pitches = play.get_pitches().map(|pitches| pitches.iter().map(|pitch| pitch.to_string()).collect::<String>()).unwrap_or_default();
+    let event = play.get_event().map(|event| event.to_string()).unwrap_or(play.get_event_text().to_string());
+    let note = play.get_note().map(|note| note.to_string()).unwrap_or_default();
+
+    format!("play,{},{},{},{count},{pitches},{event}{note}", play.get_inning(), play.get_team(), play.get_batter_id())
+}
+
+fn comment_line(comment: &str) -> String {
+    format!("com,\"{comment}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::parser::FileParser;
+    use crate::parser::Line;
+    use crate::parser::game::EventModifier;
+
+    fn game() -> Game {
+        fixtures::parse(fixtures::WRITER_FILE).remove(0)
+    }
+
+    fn event(text: &str) -> String {
+        let line = format!("play,1,0,spand001,??,,{text}");
+        match Line::parse(&line).unwrap() {
+            Line::Play { event, .. } => event.to_string(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn events_round_trip() {
+        let events = [
+            "64(1)3/GDP",
+            "8(B)84(2)/LDP",
+            "K+SB2",
+            "CS2(2E4)",
+            "S9/L9D.2-H(E7);BX3(85)",
+            "HR/F9.2-H(NR)",
+            "IW",
+            "POCS2(1361)",
+            "E6/G6.2-3;B-1",
+            "D7/L7LD.3-H;2-H;1-3",
+            "FC6/G6.3XH(62)",
+            "WP.3-H(UR);1-2",
+        ];
+
+        for text in events {
+            assert_eq!(event(text), text);
+        }
+    }
+
+    #[test]
+    fn writes_records_in_order() {
+        let lines = EventFileWriter::new().lines(&game());
+
+        assert_eq!(lines[0], "id,ANA201004050");
+        assert!(lines.contains(&"info,temp,0".to_string()));
+        assert!(lines.contains(&"info,ump1b,(none)".to_string()));
+        assert!(lines.contains(&"info,save,".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("info,innings") || line.starts_with("info,ump2b")));
+
+        let sub = lines.iter().position(|line| line.starts_with("sub,rauch001")).unwrap();
+        assert_eq!(lines[sub - 1], "play,1,1,napom001,31,BBCBB,W");
+        assert_eq!(lines[sub + 1], "play,1,1,aybae001,12,CCBS,K+WP.1-2");
+
+        let comment = lines.iter().position(|line| line == "com,\"Hudson thrown out trying for third\"").unwrap();
+        assert_eq!(lines[comment - 1], "play,1,0,hudso001,22,B.CFB>X,S9/L9D.2-H(E7);BX3(85)");
+        assert_eq!(lines.last().unwrap(), "data,er,bakes002,1");
+    }
+
+    #[test]
+    fn game_round_trips() {
+        let writer = EventFileWriter::new();
+        let original = game();
+        let written = writer.write_game(&original);
+        let reparsed = FileParser::new().parse_file(&written).unwrap().remove(0);
+
+        let events = |game: &Game| game.get_plays().iter().map(|play| play.get_event().cloned()).collect::<Vec<_>>();
+        assert_eq!(events(&reparsed), events(&original));
+        assert_eq!(reparsed.get_info_state(), original.get_info_state());
+        assert_eq!(reparsed.get_lineup_entries(), original.get_lineup_entries());
+        assert_eq!(reparsed.get_adjustments(), original.get_adjustments());
+        assert_eq!(reparsed.get_comments(), original.get_comments());
+        assert_eq!(reparsed.get_earned_runs(), original.get_earned_runs());
+
+        // once written, the game reads back exactly as it was
+        assert_eq!(FileParser::new().parse_file(&written).unwrap().remove(0), reparsed);
+        assert_eq!(writer.write_game(&reparsed), written);
+    }
+
+    #[test]
+    fn file_round_trips_as_text() {
+        assert_eq!(EventFileWriter::new().write_game(&game()), fixtures::WRITER_FILE);
+    }
+
+    #[test]
+    fn writes_corrected_event() {
+        let mut game = game();
+        let play = &mut game.get_plays_mut()[4];
+        let mut event = play.get_event().unwrap().clone();
+        event.modifiers.push(EventModifier::AppealPlay);
+        play.set_event(event);
+
+        let lines = EventFileWriter::new().lines(&game);
+        assert!(lines.contains(&"play,1,0,cuddm001,00,X,K/AP".to_string()));
+    }
+}